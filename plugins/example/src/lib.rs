@@ -0,0 +1,31 @@
+//! An example plugin, it reports types that don't have any fields besides `id`.
+//!
+//! Build it with `cargo build -p stackup-lint-example-plugin` and pass the
+//! library to `stackup-lint --plugin`.
+
+use stackup_lint::interface::Severity;
+use stackup_lint::plugin::PluginComment;
+use stackup_lint::schema::Schema;
+
+#[cfg_attr(feature = "incompatible-abi", allow(dead_code))]
+fn check(schema: &Schema) -> Vec<PluginComment> {
+    schema
+        .types
+        .iter()
+        .filter(|t| t.fields.iter().all(|f| f.name == "id"))
+        .map(|t| {
+            let message = format!("Type \"{}\" has no fields besides \"id\"", t.name);
+            PluginComment::new(t.position, Severity::Warning, message)
+                .with_code("example/empty-type")
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "incompatible-abi"))]
+stackup_lint::declare_plugin!(check);
+
+#[cfg(feature = "incompatible-abi")]
+#[no_mangle]
+pub extern "C" fn stackup_lint_plugin_abi_version() -> u32 {
+    stackup_lint::plugin::ABI_VERSION + 1
+}
@@ -1,35 +1,127 @@
 use clap::{crate_authors, crate_version, App, Arg};
-use stackup_lint::{self, interface::Format};
+use rayon::prelude::*;
+use stackup_lint::{
+    self,
+    config::Config,
+    interface::{self, CheckResult, Format},
+    plugin::Plugin,
+};
 use std::error::Error;
 use std::ffi::OsStr;
 use std::fs::File;
-use std::io::{self, Read};
-use std::path::Path;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
 
 type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
 const INPUT_EXTENSION_MESSAGE: &str =
     r#"Input file must be a graphql schema!".graphql" extension is missing"#;
+const STDIN_INPUT_MESSAGE: &str = r#""-" can't be combined with other input files"#;
 
 fn main() {
     let app = app();
     let matches = app.get_matches();
 
+    if let Some(jobs) = matches.value_of("jobs") {
+        let jobs = jobs.parse().expect("jobs is validated by clap");
+        if let Err(e) = rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()
+        {
+            eprintln!("{}", e);
+            return;
+        }
+    }
+
     let format = matches
         .value_of("format")
         .map(Format::from)
         .unwrap_or_default();
 
-    match matches.value_of("INPUT") {
-        Some("-") => try_checking(try_read_stdin(), format),
-        Some(path) => try_checking(try_read_contents(path), format),
-        _ => (),
+    let config = match matches.value_of("config").map(try_read_config) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            eprintln!("{}", e);
+            return;
+        }
+        None => Config::default(),
+    };
+
+    let plugin_paths = config.plugins.iter().cloned().chain(
+        matches
+            .values_of("plugin")
+            .into_iter()
+            .flatten()
+            .map(PathBuf::from),
+    );
+    let plugins = match plugin_paths.map(Plugin::load).collect::<Result<Vec<_>>>() {
+        Ok(plugins) => plugins,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+
+    let fix = matches.is_present("fix");
+    let summary = matches.is_present("summary");
+    let checker = Checker {
+        config: &config,
+        plugins: &plugins,
+    };
+
+    let inputs: Vec<_> = matches.values_of("INPUT").into_iter().flatten().collect();
+    match inputs.as_slice() {
+        ["-"] if fix => try_fixing_stdin(try_read_stdin(), &checker),
+        ["-"] => try_checking(try_read_stdin(), format, summary, &checker),
+        [path] if fix => try_checking(try_fixing(path, &checker), format, summary, &checker),
+        [path] => try_checking(try_read_contents(path), format, summary, &checker),
+        paths if paths.contains(&"-") => eprintln!("{}", STDIN_INPUT_MESSAGE),
+        paths => try_checking_files(paths, fix, format, summary, &checker),
     }
 }
 
-fn try_checking(r: Result<String>, format: Format) {
-    match r.map(|s| stackup_lint::check(&s)) {
+/// The configuration and plugins every input is checked with
+struct Checker<'a> {
+    config: &'a Config,
+    plugins: &'a [Plugin],
+}
+
+impl Checker<'_> {
+    /// The comments are sorted so the output doesn't depend on the rule order
+    fn check(&self, schema: &str) -> CheckResult {
+        stackup_lint::check_with_plugins(schema, self.config, self.plugins).sorted()
+    }
+}
+
+/// Applies every available fix to the file and returns the fixed contents
+fn try_fixing<P: AsRef<Path>>(path: P, checker: &Checker) -> Result<String> {
+    let contents = try_read_contents(&path)?;
+    let check_result = checker.check(&contents);
+    if !check_result.has_fixes() {
+        return Ok(contents);
+    }
+
+    let fixed = check_result.apply_fixes();
+    File::create(path)?.write_all(fixed.as_bytes())?;
+    Ok(fixed)
+}
+
+fn try_fixing_stdin(r: Result<String>, checker: &Checker) {
+    match r.map(|s| checker.check(&s)) {
+        Ok(check_result) => print!("{}", check_result.apply_fixes()),
+        Err(e) => eprintln!("{}", e),
+    }
+}
+
+fn try_checking(r: Result<String>, format: Format, summary: bool, checker: &Checker) {
+    match r.map(|s| checker.check(&s)) {
         Ok(check_result) => match format {
             Format::TTY => println!("{}", check_result),
+            Format::JSON if summary => println!(
+                "{}",
+                check_result
+                    .to_json_with_summary()
+                    .expect("failed to serialize comments")
+            ),
             Format::JSON => println!(
                 "{}",
                 check_result
@@ -41,6 +133,48 @@ fn try_checking(r: Result<String>, format: Format) {
     }
 }
 
+/// Checks, or fixes, the files concurrently and prints them in path order
+fn try_checking_files(paths: &[&str], fix: bool, format: Format, summary: bool, checker: &Checker) {
+    let mut paths = paths.to_vec();
+    paths.sort_unstable();
+    paths.dedup();
+
+    let results: Vec<_> = paths
+        .par_iter()
+        .map(|&path| {
+            let contents = if fix {
+                try_fixing(path, checker)
+            } else {
+                try_read_contents(path)
+            };
+            let result = contents
+                .map(|s| checker.check(&s))
+                .map_err(|e| e.to_string());
+            (path, result)
+        })
+        .collect();
+
+    let mut checked = Vec::with_capacity(results.len());
+    for (path, result) in results {
+        match result {
+            Ok(check_result) => checked.push((path, check_result)),
+            Err(e) => eprintln!("{}: {}", path, e),
+        }
+    }
+    match format {
+        Format::TTY => {
+            for (path, check_result) in &checked {
+                println!("{}\n{}", path, check_result);
+            }
+        }
+        Format::JSON => println!(
+            "{}",
+            interface::files_to_json(checked.iter().map(|(path, r)| (*path, r)), summary)
+                .expect("failed to serialize comments")
+        ),
+    }
+}
+
 fn try_read_contents<P: AsRef<Path>>(path: P) -> Result<String> {
     let path = path.as_ref();
     match path.extension().and_then(OsStr::to_str) {
@@ -55,6 +189,21 @@ fn try_read_contents<P: AsRef<Path>>(path: P) -> Result<String> {
     Ok(contents)
 }
 
+fn try_read_config<P: AsRef<Path>>(path: P) -> Result<Config> {
+    let path = path.as_ref();
+    let mut contents = String::new();
+    File::open(path)?.read_to_string(&mut contents)?;
+
+    let mut config = Config::from_toml(&contents)
+        .map_err(|e| format!("Invalid config file {}: {}", path.display(), e))?;
+    if let Some(dir) = path.parent() {
+        for plugin in &mut config.plugins {
+            *plugin = dir.join(&plugin);
+        }
+    }
+    Ok(config)
+}
+
 fn try_read_stdin() -> Result<String> {
     let mut contents = String::new();
 
@@ -69,8 +218,9 @@ fn app() -> App<'static, 'static> {
         .about("Checks a stackup schema to catch common mistakes")
         .arg(
             Arg::with_name("INPUT")
-                .help("Sets the input file to use")
+                .help("Sets the input files to use")
                 .required(true)
+                .multiple(true)
                 .index(1),
         )
         .arg(
@@ -82,4 +232,43 @@ fn app() -> App<'static, 'static> {
                 .default_value("tty")
                 .possible_values(&["tty", "json"]),
         )
+        .arg(
+            Arg::with_name("config")
+                .takes_value(true)
+                .short("c")
+                .long("config")
+                .value_name("FILE")
+                .help("read configuration from a toml file"),
+        )
+        .arg(
+            Arg::with_name("plugin")
+                .takes_value(true)
+                .long("plugin")
+                .value_name("PATH")
+                .multiple(true)
+                .number_of_values(1)
+                .help("load rules from a plugin shared library, can be repeated"),
+        )
+        .arg(
+            Arg::with_name("jobs")
+                .takes_value(true)
+                .short("j")
+                .long("jobs")
+                .value_name("N")
+                .validator(|n| match n.parse::<usize>() {
+                    Ok(n) if n > 0 => Ok(()),
+                    _ => Err("must be a positive number".to_string()),
+                })
+                .help("check files and rules on N threads, defaults to the number of CPUs"),
+        )
+        .arg(
+            Arg::with_name("summary")
+                .long("summary")
+                .help("include the summary, e.g. documentation coverage, in JSON output"),
+        )
+        .arg(
+            Arg::with_name("fix").long("fix").help(
+                "apply automatic fixes to the input file, or print the fixed schema for stdin",
+            ),
+        )
 }
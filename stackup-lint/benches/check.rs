@@ -0,0 +1,112 @@
+//! Checks generated schemas shaped like the ones Stackup projects grow
+//! into, every type belongs to a few earlier ones and has their inverses
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use stackup_lint::config::Config;
+use std::fmt::Write;
+
+/// A name made of letters only, so the inflector has a
+/// regular word to work with, "Qan", "Qbn", ... "Qabn", ...
+fn type_name(i: usize) -> String {
+    let mut name = String::from("Q");
+    let mut n = i;
+    loop {
+        name.push((b'a' + (n % 26) as u8) as char);
+        n /= 26;
+        if n == 0 {
+            // ending on a consonant keeps the names singular
+            name.push('n');
+            break name;
+        }
+    }
+}
+
+fn generate_schema(types: usize) -> String {
+    let mut schema = String::new();
+    writeln!(schema, "enum Status {{\n  ACTIVE\n  ARCHIVED\n}}\n").unwrap();
+
+    // the types each type belongs to
+    let parents = |i: usize| {
+        let mut parents = vec![i / 2, i / 3];
+        parents.dedup();
+        parents.retain(|&p| p < i);
+        parents
+    };
+    let mut children = vec![Vec::new(); types];
+    for i in 0..types {
+        for p in parents(i) {
+            children[p].push(i);
+        }
+    }
+
+    for (i, children) in children.iter().enumerate() {
+        let name = type_name(i);
+        writeln!(schema, "type {} {{\n  id: ID!\n  title: String!", name).unwrap();
+        writeln!(schema, "  createdOn: Date\n  status: Status!").unwrap();
+        for p in parents(i) {
+            let parent = type_name(p);
+            writeln!(
+                schema,
+                "  {}: {} @belongsTo(inverse: \"{}s\")",
+                parent.to_lowercase(),
+                parent,
+                name.to_lowercase()
+            )
+            .unwrap();
+        }
+        for &c in children {
+            let child = type_name(c);
+            writeln!(schema, "  {}s: [{}!]!", child.to_lowercase(), child).unwrap();
+        }
+        writeln!(schema, "}}\n").unwrap();
+    }
+    schema
+}
+
+fn check(c: &mut Criterion) {
+    let mut group = c.benchmark_group("check");
+    group.sample_size(10);
+    for &types in &[100, 500, 2000] {
+        let schema = generate_schema(types);
+        group.throughput(Throughput::Elements(types as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(types), &schema, |b, schema| {
+            b.iter(|| stackup_lint::check(schema))
+        });
+    }
+    group.finish();
+}
+
+/// The same schemas with a few custom rules matching names by pattern
+fn check_custom_rules(c: &mut Criterion) {
+    let config = Config::from_toml(
+        r#"
+        [[rules]]
+        code = "titles"
+        message = "{field} on {type} should be a String"
+        match = { type = "Q*", field = "*itle" }
+        require = { type = "String" }
+
+        [[rules]]
+        code = "timestamps"
+        message = "{type} should track createdOn"
+        match = { type = "Q*" }
+        require = { field = "created*" }
+        forbid = { field = "*Timestamp" }
+        "#,
+    )
+    .unwrap();
+
+    let mut group = c.benchmark_group("check_custom_rules");
+    group.sample_size(10);
+    for &types in &[100, 500, 2000] {
+        let schema = generate_schema(types);
+        group.throughput(Throughput::Elements(types as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(types), &schema, |b, schema| {
+            b.iter(|| stackup_lint::check_with_config(schema, &config))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, check, check_custom_rules);
+criterion_main!(benches);
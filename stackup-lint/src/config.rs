@@ -0,0 +1,416 @@
+use crate::interface::Severity;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Project level configuration, usually read from a `stackup-lint.toml` file
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub authentication: AuthenticationConfig,
+    pub documentation: DocumentationConfig,
+    pub generated_names: GeneratedNamesConfig,
+    pub graph: GraphConfig,
+    pub heuristics: HeuristicsConfig,
+    pub inflections: InflectionConfig,
+    pub limits: LimitsConfig,
+    pub identifiers: IdentifierConfig,
+    pub nullability: NullabilityConfig,
+    pub reserved_words: ReservedWordsConfig,
+    pub rules: Vec<CustomRuleConfig>,
+    /// Shared libraries to load rules from, relative
+    /// paths are resolved from the config file
+    pub plugins: Vec<PathBuf>,
+}
+
+impl Config {
+    pub fn from_toml(s: &str) -> crate::Result<Self> {
+        let config: Self = toml::from_str(s)?;
+        for rule in &config.rules {
+            if rule.require == Constraint::default() && rule.forbid == Constraint::default() {
+                let message = format!(r#"rule "{}" doesn't require or forbid anything"#, rule.code);
+                return Err(message.into());
+            }
+
+            // `field` is only checked on types and `type` on fields, either
+            // one would silently never be checked on the other
+            let (scope, key, misplaced) = if rule.matches.matches_fields() {
+                ("fields", "field", [&rule.require.field, &rule.forbid.field])
+            } else {
+                (
+                    "types",
+                    "type",
+                    [&rule.require.type_name, &rule.forbid.type_name],
+                )
+            };
+            for (table, pattern) in ["require", "forbid"].iter().zip(&misplaced) {
+                if pattern.is_some() {
+                    let message = format!(
+                        r#"rule "{}" matches {}, "{}.{}" doesn't apply to them"#,
+                        rule.code, scope, table, key
+                    );
+                    return Err(message.into());
+                }
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// The type marked with "@authenticate"
+///
+/// ```toml
+/// [authentication]
+/// login_field = "username"
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthenticationConfig {
+    /// Field users log in with, declared as `email: String! @unique`
+    pub login_field: String,
+}
+
+impl Default for AuthenticationConfig {
+    fn default() -> Self {
+        Self {
+            login_field: "email".to_string(),
+        }
+    }
+}
+
+/// Descriptions are shown to end users in the generated admin UI,
+/// the rule is off unless `enabled` is set
+///
+/// ```toml
+/// [documentation]
+/// enabled = true
+/// fields = true
+/// enum_values = false
+/// min_coverage = 80.0
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DocumentationConfig {
+    pub enabled: bool,
+    /// Require descriptions on fields as well as types
+    pub fields: bool,
+    /// Require descriptions on enum values as well as types
+    pub enum_values: bool,
+    /// Percentage of documented items below which
+    /// the undocumented ones are reported
+    pub min_coverage: f64,
+}
+
+impl Default for DocumentationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            fields: false,
+            enum_values: false,
+            min_coverage: 100.0,
+        }
+    }
+}
+
+/// Names Stackup generates for its API around each type, `{Type}` is
+/// replaced by the type name and `{Types}` by its plural
+///
+/// ```toml
+/// [generated_names]
+/// types = ["Create{Type}Input", "{Type}Connection"]
+/// fields = ["createdAt", "updatedAt"]
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GeneratedNamesConfig {
+    /// Type name templates
+    pub types: Vec<String>,
+    /// Fields added to every type
+    pub fields: Vec<String>,
+}
+
+impl Default for GeneratedNamesConfig {
+    fn default() -> Self {
+        let types = [
+            "Create{Type}Input",
+            "Update{Type}Input",
+            "Delete{Type}Input",
+            "Create{Type}Payload",
+            "Update{Type}Payload",
+            "Delete{Type}Payload",
+            "{Type}Connection",
+            "{Type}Edge",
+            "{Type}Filter",
+        ];
+        Self {
+            types: types.iter().map(|t| t.to_string()).collect(),
+            fields: vec!["createdAt".to_string(), "updatedAt".to_string()],
+        }
+    }
+}
+
+/// Checks on the graph formed by the types and their associations,
+/// both are off by default
+///
+/// ```toml
+/// [graph]
+/// orphans = true
+/// disconnected = true
+/// allow = ["AuditLog"]
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GraphConfig {
+    /// Report types that aren't associated with any other type
+    pub orphans: bool,
+    /// Report groups of types that aren't connected to the largest group
+    pub disconnected: bool,
+    /// Types that are expected to stand on their own, the
+    /// "@authenticate" type is always allowed
+    pub allow: Vec<String>,
+}
+
+/// Field types guessed from field names, each heuristic is toggled by its
+/// rule code. Boolean predicates are off by default
+///
+/// ```toml
+/// [heuristics]
+/// timestamps = true
+/// dates = true
+/// money = false
+/// booleans = true
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HeuristicsConfig {
+    /// `shippedAt` should be a `DateTime`
+    pub timestamps: bool,
+    /// `startsOn` and `birthDate` should be a `Date`
+    pub dates: bool,
+    /// `price`, `amount`, `cost` and `total` should be a `Decimal` rather than a `Float`
+    pub money: bool,
+    /// `Boolean` fields should read as predicates, `isPublished` or `archived`
+    pub booleans: bool,
+}
+
+impl Default for HeuristicsConfig {
+    fn default() -> Self {
+        Self {
+            timestamps: true,
+            dates: true,
+            money: true,
+            booleans: false,
+        }
+    }
+}
+
+/// Domain words the built-in inflection rules get wrong
+///
+/// ```toml
+/// [inflections]
+/// irregular = { cactus = "cacti" }
+/// uncountable = ["staff"]
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InflectionConfig {
+    /// Maps a singular word to its plural form
+    pub irregular: BTreeMap<String, String>,
+    /// Words that are spelled the same in their singular and plural forms
+    pub uncountable: Vec<String>,
+}
+
+/// Limits for the table, column and index names derived from the schema
+///
+/// ```toml
+/// [identifiers]
+/// max_length = 63
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IdentifierConfig {
+    /// Longest identifier in bytes, PostgreSQL truncates anything longer than 63
+    pub max_length: usize,
+}
+
+impl Default for IdentifierConfig {
+    fn default() -> Self {
+        Self { max_length: 63 }
+    }
+}
+
+/// Size and complexity limits, each one is only checked once it's set
+///
+/// ```toml
+/// [limits]
+/// max_fields = 30
+/// max_associations = 10
+/// max_enum_values = 50
+/// max_types = 100
+/// max_association_depth = 4
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// Fields per object type
+    pub max_fields: Option<usize>,
+    /// Fields per object type that refer to another object type
+    pub max_associations: Option<usize>,
+    /// Values per enum
+    pub max_enum_values: Option<usize>,
+    /// Object and enum types in the whole schema
+    pub max_types: Option<usize>,
+    /// "@belongsTo" associations that can be followed one after another,
+    /// `Review -> Book -> Author` is a chain of 2. Every type in a cycle
+    /// of associations adds to the depth once
+    pub max_association_depth: Option<usize>,
+}
+
+/// ```toml
+/// [nullability]
+/// associations = "required"
+/// unique = "any"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NullabilityConfig {
+    pub associations: AssociationPolicy,
+    pub unique: UniquePolicy,
+}
+
+/// Whether "@belongsTo" fields should be declared non-null
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AssociationPolicy {
+    /// Both `author: Author!` and `author: Author` are allowed
+    #[default]
+    Any,
+    /// Every association should be non-null, `author: Author!`
+    Required,
+    /// Every association should be nullable, `author: Author`
+    Optional,
+}
+
+/// Whether "@unique" fields should be declared non-null, a unique
+/// index still allows any number of rows to be null
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UniquePolicy {
+    /// Every unique field should be non-null, `email: String! @unique`
+    #[default]
+    Required,
+    /// Both `email: String! @unique` and `email: String @unique` are allowed
+    Any,
+}
+
+/// Where generated names must not collide with reserved words
+///
+/// ```toml
+/// [reserved_words]
+/// targets = ["postgresql", "ruby"]
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReservedWordsConfig {
+    pub targets: Vec<ReservedWordTarget>,
+}
+
+impl Default for ReservedWordsConfig {
+    fn default() -> Self {
+        Self {
+            targets: vec![
+                ReservedWordTarget::PostgreSQL,
+                ReservedWordTarget::Ruby,
+                ReservedWordTarget::JavaScript,
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReservedWordTarget {
+    PostgreSQL,
+    Ruby,
+    #[serde(alias = "typescript")]
+    JavaScript,
+}
+
+impl fmt::Display for ReservedWordTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::PostgreSQL => write!(f, "PostgreSQL"),
+            Self::Ruby => write!(f, "Ruby"),
+            Self::JavaScript => write!(f, "JavaScript"),
+        }
+    }
+}
+
+/// A house rule declared in the config file. The rule applies to the types
+/// that match, or to their fields once a field matcher is given. Names are
+/// matched with `*` wildcards
+///
+/// ```toml
+/// [[rules]]
+/// code = "url-fields"
+/// severity = "error"
+/// message = "URLs are stored as strings"
+/// match = { field = "*Url" }
+/// require = { type = "String" }
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomRuleConfig {
+    pub code: String,
+    #[serde(default = "default_severity")]
+    pub severity: Severity,
+    /// `{type}` and `{field}` are replaced by the matching names
+    pub message: String,
+    #[serde(rename = "match", default)]
+    pub matches: Matcher,
+    #[serde(default)]
+    pub require: Constraint,
+    #[serde(default)]
+    pub forbid: Constraint,
+}
+
+fn default_severity() -> Severity {
+    Severity::Warning
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Matcher {
+    /// Type name pattern
+    #[serde(rename = "type")]
+    pub type_name: Option<String>,
+    /// Directive on the type, `authenticate`
+    pub directive: Option<String>,
+    /// Field name pattern
+    pub field: Option<String>,
+    /// Field type name pattern, without list or non-null wrappers
+    pub field_type: Option<String>,
+    /// Directive on the field, `belongsTo`
+    pub field_directive: Option<String>,
+}
+
+impl Matcher {
+    /// Rules that match on a field check fields, the others check types
+    pub fn matches_fields(&self) -> bool {
+        self.field.is_some() || self.field_type.is_some() || self.field_directive.is_some()
+    }
+}
+
+/// What a matching type or field must have, or must not have. `field` only
+/// applies to types, `type` only to fields
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Constraint {
+    /// Field name pattern
+    pub field: Option<String>,
+    pub directive: Option<String>,
+    /// Field type name pattern, without list or non-null wrappers
+    #[serde(rename = "type")]
+    pub type_name: Option<String>,
+}
@@ -0,0 +1,28 @@
+use crate::inflection::Inflector;
+use heck::SnakeCase;
+
+/// `WidgetAuthor` is stored in the `widget_authors` table
+pub fn table_name(type_name: &str, inflector: &Inflector) -> String {
+    inflector.pluralize(type_name).to_snake_case()
+}
+
+/// `WidgetAuthor` rows are referred to as `widget_author`
+pub fn model_name(type_name: &str, inflector: &Inflector) -> String {
+    inflector.singularize(type_name).to_snake_case()
+}
+
+/// `firstName` is stored in the `first_name` column
+pub fn column_name(field_name: &str) -> String {
+    field_name.to_snake_case()
+}
+
+/// `widgetAuthor: WidgetAuthor! @belongsTo` is stored in the `widget_author_id` column
+pub fn foreign_key_column(field_name: &str) -> String {
+    format!("{}_id", column_name(field_name))
+}
+
+/// Indexes are named after the table and the indexed column,
+/// `index_books_on_author_id`
+pub fn index_name(table_name: &str, column_name: &str) -> String {
+    format!("index_{}_on_{}", table_name, column_name)
+}
@@ -0,0 +1,166 @@
+use crate::interface::{Edit, Pos};
+
+/// Applies every edit it can locate to the schema, edits that
+/// overlap an earlier one are skipped
+pub(crate) fn apply<'a>(schema: &str, edits: impl Iterator<Item = &'a Edit>) -> String {
+    let mut ranges: Vec<((usize, usize), &Edit)> = Vec::new();
+    for edit in edits {
+        if let Some((start, end)) = locate(schema, edit) {
+            if ranges.iter().all(|((s, e), _)| end <= *s || start >= *e) {
+                ranges.push(((start, end), edit));
+            }
+        }
+    }
+    ranges.sort_by_key(|(range, _)| *range);
+
+    let mut fixed = String::with_capacity(schema.len());
+    let mut offset = 0;
+    for ((start, end), edit) in ranges {
+        fixed.push_str(&schema[offset..start]);
+        fixed.push_str(&edit.replacement);
+        offset = end;
+    }
+    fixed.push_str(&schema[offset..]);
+    fixed
+}
+
+/// Finds the byte range of the first run of tokens matching `edit.original`,
+/// starting from the line of `edit.pos`. Comments and strings are skipped so
+/// a description mentioning a name is never rewritten.
+fn locate(schema: &str, edit: &Edit) -> Option<(usize, usize)> {
+    let original: Vec<_> = tokens(&edit.original, 0)
+        .map(|(start, end)| &edit.original[start..end])
+        .collect();
+    if original.is_empty() {
+        return None;
+    }
+
+    let source: Vec<_> = tokens(schema, line_offset(schema, edit.pos)?).collect();
+    source
+        .windows(original.len())
+        .find(|window| {
+            window
+                .iter()
+                .zip(&original)
+                .all(|((start, end), token)| &schema[*start..*end] == *token)
+        })
+        .map(|window| (window[0].0, window[window.len() - 1].1))
+}
+
+fn line_offset(schema: &str, pos: Pos) -> Option<usize> {
+    if pos.line <= 1 {
+        return Some(0);
+    }
+    schema
+        .match_indices('\n')
+        .nth(pos.line - 2)
+        .map(|(i, _)| i + 1)
+}
+
+/// Byte ranges of the names and punctuators in `s` from `offset` onwards
+fn tokens(s: &str, offset: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let bytes = s.as_bytes();
+    let mut i = offset;
+
+    std::iter::from_fn(move || {
+        while i < bytes.len() {
+            let start = i;
+            match bytes[i] {
+                b'#' => {
+                    while i < bytes.len() && bytes[i] != b'\n' {
+                        i += 1;
+                    }
+                }
+                b'"' if bytes[i..].starts_with(br#"""""#) => {
+                    i += 3;
+                    while i < bytes.len() && !bytes[i..].starts_with(br#"""""#) {
+                        i += if bytes[i] == b'\\' { 2 } else { 1 };
+                    }
+                    i += 3;
+                }
+                b'"' => {
+                    i += 1;
+                    while i < bytes.len() && bytes[i] != b'"' && bytes[i] != b'\n' {
+                        i += if bytes[i] == b'\\' { 2 } else { 1 };
+                    }
+                    i += 1;
+                }
+                b if b.is_ascii_alphanumeric() || b == b'_' || b == b'-' => {
+                    while i < bytes.len()
+                        && (bytes[i].is_ascii_alphanumeric()
+                            || bytes[i] == b'_'
+                            || bytes[i] == b'.'
+                            || bytes[i] == b'-')
+                    {
+                        i += 1;
+                    }
+                    return Some((start, i));
+                }
+                b if b.is_ascii_whitespace() || b == b',' => i += 1,
+                _ => {
+                    i += s[i..].chars().next().map_or(1, char::len_utf8);
+                    return Some((start, i));
+                }
+            }
+        }
+        None
+    })
+    .map(move |(start, end)| (start, end.min(bytes.len())))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn edit(line: usize, original: &str, replacement: &str) -> Edit {
+        Edit::new(Pos { line, column: 1 }, original, replacement)
+    }
+
+    #[test]
+    fn test_apply() {
+        let schema = r#"type book_author {
+  "the book_author's name"
+  first_name: String! # first_name
+  books: [book_author!]!
+}
+"#;
+        let edits = [
+            edit(1, "book_author", "BookAuthor"),
+            edit(2, "first_name", "firstName"),
+            edit(4, "book_author", "BookAuthor"),
+            edit(4, "books: [book_author!]!", "authors: [BookAuthor!]!"),
+        ];
+
+        assert_eq!(
+            apply(schema, edits.iter()),
+            r#"type BookAuthor {
+  "the book_author's name"
+  firstName: String! # first_name
+  books: [BookAuthor!]!
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_apply_multiple_tokens() {
+        let schema = "type Book {\n  authorId :  ID!\n}\n";
+        let edits = [edit(2, "authorId: ID!", "author: Author! @belongsTo")];
+
+        assert_eq!(
+            apply(schema, edits.iter()),
+            "type Book {\n  author: Author! @belongsTo\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_non_ascii_block_string() {
+        let schema = "type Book {\n  \"\"\"Le café\"\"\"\n  first_name: String!\n}\n";
+        let edits = [edit(1, "first_name", "firstName")];
+
+        assert_eq!(
+            apply(schema, edits.iter()),
+            "type Book {\n  \"\"\"Le café\"\"\"\n  firstName: String!\n}\n"
+        );
+    }
+}
@@ -0,0 +1,383 @@
+use crate::config::InflectionConfig;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+lazy_static! {
+    /// Pluralization rules, the first matching rule wins
+    static ref PLURALS: Vec<(Regex, &'static str)> = rules(&[
+        (r"(?i)(quiz)$", "${1}zes"),
+        (r"(?i)^(oxen)$", "${1}"),
+        (r"(?i)^(ox)$", "${1}en"),
+        (r"(?i)^(m|l)ouse$", "${1}ice"),
+        (r"(?i)(matr|vert|ind)(?:ix|ex)$", "${1}ices"),
+        (r"(?i)(x|ch|ss|sh)$", "${1}es"),
+        (r"(?i)([^aeiouy]|qu)y$", "${1}ies"),
+        (r"(?i)(hive)$", "${1}s"),
+        (r"(?i)(wol|hal|el|cal|scar|dwar|lea|loa|thie|shea)f$", "${1}ves"),
+        (r"(?i)(kni|^wi|^li)fe$", "${1}ves"),
+        (r"(?i)sis$", "ses"),
+        (r"(?i)(buffal|tomat|potat|her|ech)o$", "${1}oes"),
+        (r"(?i)^(ax|test)is$", "${1}es"),
+        (r"(?i)s$", "ses"),
+        (r"$", "s"),
+    ]);
+
+    /// Singularization rules, the first matching rule wins
+    static ref SINGULARS: Vec<(Regex, &'static str)> = rules(&[
+        (r"(?i)(database)s$", "${1}"),
+        (r"(?i)(quiz)zes$", "${1}"),
+        (r"(?i)(matr)ices$", "${1}ix"),
+        (r"(?i)(vert|ind)ices$", "${1}ex"),
+        (r"(?i)^(ox)en", "${1}"),
+        (r"(?i)(alias|status|campus|virus)(es)?$", "${1}"),
+        (r"(?i)^(a)x[ie]s$", "${1}xis"),
+        (r"(?i)(cris|test)(is|es)$", "${1}is"),
+        (r"(?i)(shoe)s$", "${1}"),
+        (r"(?i)(o)es$", "${1}"),
+        (r"(?i)(bus)(es)?$", "${1}"),
+        (r"(?i)(hous|caus|blous|spous|abus|excus|^us)es$", "${1}e"),
+        (r"(?i)(us)es$", "${1}"),
+        (r"(?i)^(m|l)ice$", "${1}ouse"),
+        (r"(?i)(x|ch|ss|sh)es$", "${1}"),
+        // "cookies" and "ties" end in -ie rather than -y
+        (r"(?i)(^[dlpt]|cook|rook|brown|self|hood|calor|prair|gen|mov)ies$", "${1}ie"),
+        (r"(?i)(s)eries$", "${1}eries"),
+        (r"(?i)([^aeiouy]|qu)ies$", "${1}y"),
+        (r"(?i)(wol|hal|el|cal|scar|dwar|lea|loa|thie|shea)ves$", "${1}f"),
+        (r"(?i)(tive)s$", "${1}"),
+        (r"(?i)(hive)s$", "${1}"),
+        // "drives" and "waves" only lose their "s"
+        (r"(?i)(kni|^wi|^li)ves$", "${1}fe"),
+        (r"(?i)(^analy)(sis|ses)$", "${1}sis"),
+        (r"(?i)((a)naly|(d)iagno|(p)arenthe|(p)rogno|(s)ynop|(t)he)(sis|ses)$", "${1}sis"),
+        (r"(?i)(n)ews$", "${1}ews"),
+        (r"(?i)(ss)$", "${1}"),
+        (r"(?i)s$", ""),
+    ]);
+
+    /// Singular and plural pairs that don't follow any rule
+    static ref IRREGULARS: Vec<(&'static str, &'static str)> = vec![
+        ("person", "people"),
+        ("man", "men"),
+        ("woman", "women"),
+        ("child", "children"),
+        ("tooth", "teeth"),
+        ("foot", "feet"),
+        ("goose", "geese"),
+        ("sex", "sexes"),
+        ("move", "moves"),
+        ("zombie", "zombies"),
+        ("criterion", "criteria"),
+        // singular words ending in -s that the rules would strip
+        ("gas", "gases"),
+        ("lens", "lenses"),
+        ("canvas", "canvases"),
+        ("atlas", "atlases"),
+        ("bias", "biases"),
+        ("iris", "irises"),
+        ("bonus", "bonuses"),
+        ("census", "censuses"),
+        ("corpus", "corpora"),
+        ("genus", "genera"),
+        // "axes" and "bases" are the plural of two words each, the
+        // later pair is the one they singularize to
+        ("axis", "axes"),
+        ("axe", "axes"),
+        ("basis", "bases"),
+        ("base", "bases"),
+    ];
+
+    /// Words that are spelled the same in their singular and plural forms
+    static ref UNCOUNTABLES: Vec<&'static str> = vec![
+        "equipment",
+        "information",
+        "rice",
+        "money",
+        "species",
+        "series",
+        "fish",
+        "sheep",
+        "jeans",
+        "police",
+        "news",
+        "data",
+        "metadata",
+        "feedback",
+        "software",
+        "chassis",
+        "apparatus",
+    ];
+}
+
+fn rules(rules: &[(&str, &'static str)]) -> Vec<(Regex, &'static str)> {
+    rules
+        .iter()
+        .map(|(pattern, replacement)| (Regex::new(pattern).unwrap(), *replacement))
+        .collect()
+}
+
+/// Converts words between their singular and plural forms.
+///
+/// Only the last word of a PascalCase or camelCase identifier is inflected,
+/// so `SalesPerson` becomes `SalesPeople` and `bookCategory` becomes
+/// `bookCategories`
+#[derive(Debug, Clone)]
+pub struct Inflector {
+    plurals: HashMap<String, String>,
+    singulars: HashMap<String, String>,
+    uncountables: HashSet<String>,
+}
+
+impl Default for Inflector {
+    fn default() -> Self {
+        Self::new(&InflectionConfig::default())
+    }
+}
+
+impl Inflector {
+    /// Builds an inflector from the built-in word lists, extended
+    /// (and overridden) by the project's configuration
+    pub fn new(config: &InflectionConfig) -> Self {
+        let mut plurals = HashMap::new();
+        let mut singulars = HashMap::new();
+        let mut uncountables: HashSet<_> = UNCOUNTABLES.iter().map(|w| w.to_string()).collect();
+
+        let irregulars = IRREGULARS
+            .iter()
+            .map(|(singular, plural)| (singular.to_string(), plural.to_string()))
+            .chain(
+                config
+                    .irregular
+                    .iter()
+                    .map(|(singular, plural)| (singular.to_lowercase(), plural.to_lowercase())),
+            );
+        for (singular, plural) in irregulars {
+            uncountables.remove(&singular);
+            uncountables.remove(&plural);
+            plurals.insert(singular.clone(), plural.clone());
+            singulars.insert(plural, singular);
+        }
+        uncountables.extend(config.uncountable.iter().map(|w| w.to_lowercase()));
+
+        Self {
+            plurals,
+            singulars,
+            uncountables,
+        }
+    }
+
+    pub fn pluralize(&self, word: &str) -> String {
+        self.inflect(word, &self.plurals, &PLURALS)
+    }
+
+    pub fn singularize(&self, word: &str) -> String {
+        self.inflect(word, &self.singulars, &SINGULARS)
+    }
+
+    /// Returns true if the last word of `word` is already in its plural form
+    pub fn is_plural(&self, word: &str) -> bool {
+        let (_, last_word) = split_last_word(word);
+        let last_word = last_word.to_lowercase();
+        self.uncountables.contains(&last_word)
+            || self.singulars.contains_key(&last_word)
+            || (!self.plurals.contains_key(&last_word)
+                && self.pluralize(&self.singularize(&last_word)) == last_word)
+    }
+
+    /// Returns true if the last word of `word` is already in its singular form
+    pub fn is_singular(&self, word: &str) -> bool {
+        let (_, last_word) = split_last_word(word);
+        let last_word = last_word.to_lowercase();
+        self.uncountables.contains(&last_word)
+            || self.plurals.contains_key(&last_word)
+            || (!self.singulars.contains_key(&last_word)
+                && self.singularize(&self.pluralize(&last_word)) == last_word)
+    }
+
+    /// Returns true if the last word of `word` is in the irregular or
+    /// uncountable lists, rather than inflected by the rules
+    pub fn is_irregular(&self, word: &str) -> bool {
+        let (_, last_word) = split_last_word(word);
+        let last_word = last_word.to_lowercase();
+        self.uncountables.contains(&last_word)
+            || self.plurals.contains_key(&last_word)
+            || self.singulars.contains_key(&last_word)
+    }
+
+    fn inflect(
+        &self,
+        word: &str,
+        irregulars: &HashMap<String, String>,
+        rules: &[(Regex, &'static str)],
+    ) -> String {
+        let (prefix, last_word) = split_last_word(word);
+        let lowercase = last_word.to_lowercase();
+
+        let inflected = if lowercase.is_empty() || self.uncountables.contains(&lowercase) {
+            lowercase
+        } else if let Some(irregular) = irregulars.get(&lowercase) {
+            irregular.clone()
+        } else if irregulars.values().any(|irregular| *irregular == lowercase) {
+            // already inflected, "people" stays "people"
+            lowercase
+        } else {
+            rules
+                .iter()
+                .find(|(pattern, _)| pattern.is_match(&lowercase))
+                .map(|(pattern, replacement)| {
+                    pattern.replace(&lowercase, *replacement).into_owned()
+                })
+                .unwrap_or(lowercase)
+        };
+
+        format!("{}{}", prefix, match_case(last_word, &inflected))
+    }
+}
+
+/// Splits an identifier before the start of its last word. A run of
+/// capitals is one word, the way `to_snake_case` sees it, so `userFAQ`
+/// splits into `user` and `FAQ`, and `FAQItem` into `FAQ` and `Item`
+fn split_last_word(word: &str) -> (&str, &str) {
+    let chars: Vec<_> = word.char_indices().collect();
+    let index = (1..chars.len())
+        .rev()
+        .find(|&i| {
+            let prev = chars[i - 1].1;
+            let c = chars[i].1;
+            let next = chars.get(i + 1).map(|&(_, next)| next);
+            prev == '_'
+                || (c.is_uppercase()
+                    && (!prev.is_uppercase() || next.is_some_and(char::is_lowercase)))
+        })
+        .map_or(0, |i| chars[i].0);
+    word.split_at(index)
+}
+
+/// Applies the capitalization of `original` to `inflected`
+fn match_case(original: &str, inflected: &str) -> String {
+    if original.len() > 1 && original.chars().all(|c| !c.is_lowercase()) {
+        inflected.to_uppercase()
+    } else if original.chars().next().is_some_and(char::is_uppercase) {
+        let mut chars = inflected.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect())
+            .unwrap_or_default()
+    } else {
+        inflected.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use heck::MixedCase;
+
+    #[test]
+    fn test_pluralize() {
+        let inflector = Inflector::default();
+        let cases = [
+            ("book", "books"),
+            ("Category", "Categories"),
+            ("Box", "Boxes"),
+            ("bus", "buses"),
+            ("address", "addresses"),
+            ("status", "statuses"),
+            ("Person", "People"),
+            ("SalesPerson", "SalesPeople"),
+            ("child", "children"),
+            ("wife", "wives"),
+            ("half", "halves"),
+            ("analysis", "analyses"),
+            ("hero", "heroes"),
+            ("Equipment", "Equipment"),
+            ("WidgetAuthor", "WidgetAuthors"),
+            ("People", "People"),
+            ("day", "days"),
+            ("query", "queries"),
+        ];
+        for (singular, plural) in cases.iter() {
+            assert_eq!(inflector.pluralize(singular), *plural);
+        }
+    }
+
+    #[test]
+    fn test_singularize() {
+        let inflector = Inflector::default();
+        let cases = [
+            ("books", "book"),
+            ("categories", "category"),
+            ("boxes", "box"),
+            ("buses", "bus"),
+            ("addresses", "address"),
+            ("statuses", "status"),
+            ("people", "person"),
+            ("editedBooks", "editedBook"),
+            ("wives", "wife"),
+            ("analyses", "analysis"),
+            ("movies", "movie"),
+            ("news", "news"),
+            ("person", "person"),
+            ("address", "address"),
+            ("cactuses", "cactus"),
+            ("campuses", "campus"),
+            ("warehouses", "warehouse"),
+            ("uses", "use"),
+        ];
+        for (plural, singular) in cases.iter() {
+            assert_eq!(inflector.singularize(plural), *singular);
+        }
+    }
+
+    #[test]
+    fn test_is_singular() {
+        let inflector = Inflector::default();
+        let words = [
+            "Drive", "Wave", "Cookie", "Tie", "Base", "Axe", "Gas", "Lens", "Canvas", "Atlas",
+            "Bias", "Iris", "Bonus", "Valve", "Wife", "Half", "Status",
+        ];
+        for word in words.iter() {
+            assert!(inflector.is_singular(word), "{}", word);
+            assert!(!inflector.is_plural(word), "{}", word);
+            assert_eq!(inflector.singularize(&inflector.pluralize(word)), *word);
+        }
+        // "axes" and "bases" singularize to "axe" and "base"
+        assert!(inflector.is_singular("Axis"));
+        assert!(inflector.is_singular("Basis"));
+        assert!(inflector.is_singular("Chassis"));
+        assert_eq!(inflector.singularize("cookies"), "cookie");
+        assert_eq!(inflector.singularize("drives"), "drive");
+        assert_eq!(inflector.singularize("ties"), "tie");
+        assert_eq!(inflector.singularize("Gases"), "Gas");
+        assert_eq!(inflector.pluralize("Lens"), "Lenses");
+    }
+
+    #[test]
+    fn test_acronyms() {
+        let inflector = Inflector::default();
+        assert_eq!(split_last_word("FAQ"), ("", "FAQ"));
+        assert_eq!(split_last_word("userFAQ"), ("user", "FAQ"));
+        assert_eq!(split_last_word("FAQItem"), ("FAQ", "Item"));
+        assert_eq!(split_last_word("book_author"), ("book_", "author"));
+        assert_eq!(inflector.pluralize("FAQ"), "FAQS");
+        assert_eq!(inflector.pluralize("FAQ").to_mixed_case(), "faqs");
+        assert_eq!(inflector.pluralize("FAQItem"), "FAQItems");
+    }
+
+    #[test]
+    fn test_config_overrides() {
+        let mut config = InflectionConfig::default();
+        config
+            .irregular
+            .insert("cactus".to_string(), "cacti".to_string());
+        config.uncountable.push("staff".to_string());
+        let inflector = Inflector::new(&config);
+
+        assert_eq!(inflector.pluralize("Cactus"), "Cacti");
+        assert_eq!(inflector.singularize("cacti"), "cactus");
+        assert_eq!(inflector.pluralize("staff"), "staff");
+        assert!(inflector.is_plural("cacti"));
+        assert!(inflector.is_singular("cactus"));
+    }
+}
@@ -1,12 +1,14 @@
 pub use graphql_parser::Pos;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json;
 use std::convert::From;
 use std::fmt;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Severity {
+    #[serde(alias = "warning")]
     Warning,
+    #[serde(alias = "error")]
     Error,
 }
 
@@ -39,6 +41,12 @@ pub struct PositionedComment {
     end_pos: Pos,
     #[serde(flatten)]
     comment: Comment,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related: Vec<RelatedLocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fix: Option<Fix>,
 }
 
 impl PositionedComment {
@@ -47,24 +55,163 @@ impl PositionedComment {
             end_pos: start_pos,
             start_pos,
             comment,
+            code: None,
+            related: Vec::new(),
+            fix: None,
         }
     }
+
+    /// Tags the comment with the code of the rule that produced it,
+    /// the code is what the rule is toggled by in the config
+    pub fn with_code(mut self, code: &str) -> Self {
+        self.code = Some(code.to_string());
+        self
+    }
+
+    /// Attaches an automatic fix for this comment
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
+    /// Points at another location that contributes to this comment
+    pub fn with_related(mut self, pos: Pos, message: String) -> Self {
+        self.related.push(RelatedLocation { pos, message });
+        self
+    }
 }
 
 impl fmt::Display for PositionedComment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] ({}", self.start_pos, self.comment.severity)?;
+        if let Some(ref code) = self.code {
+            write!(f, "[{}]", code)?;
+        }
+        write!(f, " - {})", self.comment.message)?;
+        for related in &self.related {
+            write!(f, "\n    [{}] {}", related.pos, related.message)?;
+        }
+        if let Some(ref fix) = self.fix {
+            write!(f, "\n    fix: {}", fix.message)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RelatedLocation {
+    #[serde(with = "json::PosDef")]
+    pos: Pos,
+    message: String,
+}
+
+/// A set of edits that resolves a comment
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Fix {
+    message: String,
+    edits: Vec<Edit>,
+}
+
+impl Fix {
+    pub fn new(message: String) -> Self {
+        Self {
+            message,
+            edits: Vec::new(),
+        }
+    }
+
+    /// Renames an identifier declared at `pos`
+    pub fn rename(pos: Pos, original: &str, replacement: &str) -> Self {
+        let message = format!(r#"rename "{}" to "{}""#, original, replacement);
+        Self::new(message).with_edit(Edit::new(pos, original, replacement))
+    }
+
+    pub fn with_edit(mut self, edit: Edit) -> Self {
+        self.edits.push(edit);
+        self
+    }
+}
+
+/// Replaces the first occurrence of `original` found from the start of the
+/// line at `pos`. `original` is compared token by token, so it can span
+/// several tokens (`authorId: ID!`) regardless of the whitespace between them
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Edit {
+    #[serde(with = "json::PosDef")]
+    pub(crate) pos: Pos,
+    pub(crate) original: String,
+    pub(crate) replacement: String,
+}
+
+impl Edit {
+    pub fn new(pos: Pos, original: &str, replacement: &str) -> Self {
+        Self {
+            pos,
+            original: original.to_string(),
+            replacement: replacement.to_string(),
+        }
+    }
+}
+
+/// Schema wide figures reported after the comments
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Summary {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documentation: Option<Coverage>,
+}
+
+impl Summary {
+    /// Whether no rule reported a figure, e.g. documentation coverage is off
+    pub fn is_empty(&self) -> bool {
+        self.documentation.is_none()
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref coverage) = self.documentation {
+            write!(f, "Documentation coverage: {}", coverage)?;
+        }
+        Ok(())
+    }
+}
+
+/// How many of the checked items are documented
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Coverage {
+    pub documented: usize,
+    pub total: usize,
+}
+
+impl Coverage {
+    /// An empty schema is fully covered
+    pub fn percentage(self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.documented as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+impl fmt::Display for Coverage {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "[{}] ({} - {})",
-            self.start_pos, self.comment.severity, self.comment.message
+            "{:.1}% ({} of {} documented)",
+            self.percentage(),
+            self.documented,
+            self.total
         )
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub struct CheckResult {
+    #[serde(skip)]
     schema: String,
     comments: Vec<PositionedComment>,
+    summary: Summary,
 }
 
 impl fmt::Display for CheckResult {
@@ -77,21 +224,95 @@ impl fmt::Display for CheckResult {
                 message.push_str(&format!("{}\n", c));
             }
             write!(f, "{}", message)
+        }?;
+        if !self.summary.is_empty() {
+            let separator = if self.comments.is_empty() { "" } else { "\n" };
+            write!(f, "{}{}", separator, self.summary)?;
         }
+        Ok(())
     }
 }
 
 impl CheckResult {
     pub fn new(schema: String, comments: Vec<PositionedComment>) -> Self {
-        Self { schema, comments }
+        Self {
+            schema,
+            comments,
+            summary: Summary::default(),
+        }
+    }
+
+    pub fn with_summary(mut self, summary: Summary) -> Self {
+        self.summary = summary;
+        self
+    }
+
+    pub fn summary(&self) -> &Summary {
+        &self.summary
+    }
+
+    /// Orders the comments by position, then by the code of the rule
+    /// that reported them
+    pub fn sorted(mut self) -> Self {
+        self.comments
+            .sort_by(|a, b| (a.start_pos, &a.code).cmp(&(b.start_pos, &b.code)));
+        self
     }
 
+    /// Returns the schema with every available fix applied
+    pub fn apply_fixes(&self) -> String {
+        let edits = self
+            .comments
+            .iter()
+            .filter_map(|c| c.fix.as_ref())
+            .flat_map(|fix| fix.edits.iter());
+        crate::fix::apply(&self.schema, edits)
+    }
+
+    pub fn has_fixes(&self) -> bool {
+        self.comments.iter().any(|c| c.fix.is_some())
+    }
+
+    /// The comments, `[...]`, whether or not a rule reported a summary
     pub fn to_json(&self) -> crate::Result<String> {
         serde_json::to_string_pretty(&self.comments).map_err(|e| e.into())
     }
+
+    /// `{ "comments": [...], "summary": {...} }`, the summary is `{}`
+    /// when no rule reported one
+    pub fn to_json_with_summary(&self) -> crate::Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.into())
+    }
+}
+
+/// The results of several files, `[{ "file": ..., "comments": [...] }, ...]`,
+/// each with its `"summary"` too when `with_summary` is set
+pub fn files_to_json<'a, I>(results: I, with_summary: bool) -> crate::Result<String>
+where
+    I: IntoIterator<Item = (&'a str, &'a CheckResult)>,
+{
+    #[derive(Serialize)]
+    struct FileResult<'a> {
+        file: &'a str,
+        comments: &'a [PositionedComment],
+        #[serde(skip_serializing_if = "Option::is_none")]
+        summary: Option<&'a Summary>,
+    }
+
+    let files: Vec<_> = results
+        .into_iter()
+        .map(|(file, result)| FileResult {
+            file,
+            comments: &result.comments,
+            summary: Some(&result.summary).filter(|_| with_summary),
+        })
+        .collect();
+    serde_json::to_string_pretty(&files).map_err(|e| e.into())
 }
 
+#[derive(Default)]
 pub enum Format {
+    #[default]
     TTY,
     JSON,
 }
@@ -105,16 +326,10 @@ impl From<&str> for Format {
     }
 }
 
-impl Default for Format {
-    fn default() -> Self {
-        Format::TTY
-    }
-}
-
-mod json {
+pub(crate) mod json {
     use super::*;
 
-    #[derive(Serialize)]
+    #[derive(Serialize, Deserialize)]
     #[serde(remote = "Pos")]
     pub struct PosDef {
         pub line: usize,
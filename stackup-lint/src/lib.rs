@@ -1,16 +1,23 @@
-use graphql_parser;
 use lazy_static::lazy_static;
+use rayon::prelude::*;
 use regex::Regex;
 use std::collections::HashSet;
 use std::error::Error;
 
+pub mod config;
+pub mod database;
+mod fix;
+pub mod inflection;
 pub mod interface;
+pub mod plugin;
 mod rules;
+pub mod schema;
+use config::Config;
+use inflection::Inflector;
 use interface::{CheckResult, Comment, Pos, PositionedComment, Severity};
-use rules::{
-    associations::check_associations, id::check_types_for_id_field,
-    list_of_scalars::check_for_list_of_scalars,
-};
+use plugin::Plugin;
+use rules::{index::Index, Context, Report};
+use schema::Schema;
 
 type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
 
@@ -31,38 +38,65 @@ lazy_static! {
     static ref REGEX: Regex = Regex::new(
         r"^schema parse error: Parse error at (?P<line>\d+):(?P<column>\d+)(?x)
         \n(?P<unexpected>.*)
-        \n(?P<expected>.*)$",
+        (?:\n(?P<expected>.*))?$",
     )
     .unwrap();
 }
 
 pub fn check(schema: &str) -> CheckResult {
+    check_with_config(schema, &Config::default())
+}
+
+pub fn check_with_config(schema: &str, config: &Config) -> CheckResult {
+    check_with_plugins(schema, config, &[])
+}
+
+/// Runs the plugins' rules after the built-in and custom ones. Rules are
+/// run concurrently on rayon's current thread pool, their comments are
+/// still reported in rule order
+pub fn check_with_plugins(schema: &str, config: &Config, plugins: &[Plugin]) -> CheckResult {
     let document_result = graphql_parser::parse_schema(schema).map_err(|e| e.to_string());
 
     match document_result {
         Ok(document) => {
-            let defns = document.definitions;
-
-            let mut comments = Vec::new();
+            let inflector = Inflector::new(&config.inflections);
+            let schema_model = Schema::new(&document.definitions, &inflector);
+            let index = Index::new(&schema_model, &inflector);
 
-            comments.append(&mut check_associations(&defns));
-            comments.append(&mut check_types_for_id_field(&defns));
-            comments.append(&mut check_for_list_of_scalars(&defns));
+            let context = Context {
+                schema: &schema_model,
+                index: &index,
+                config,
+                inflector: &inflector,
+            };
+            let report = rules::all(config, plugins)
+                .par_iter()
+                .map(|rule| {
+                    let mut report = Report::default();
+                    rule.check(&context, &mut report);
+                    report
+                })
+                .reduce(Report::default, Report::merge);
 
-            CheckResult::new(schema.to_string(), comments)
+            CheckResult::new(schema.to_string(), report.comments).with_summary(report.summary)
         }
         Err(e) => {
             let captures = REGEX.captures(e.trim()).expect("no captures");
             let line: usize = captures["line"].parse().unwrap();
             let column: usize = captures["column"].parse().unwrap();
-            let expected = &captures["expected"];
             let unexpected = &captures["unexpected"];
 
-            let message = format!(
-                r"    {}
+            // an error that isn't about a token, e.g. an int that doesn't
+            // fit an i64, has no expected token
+            let message = match captures.name("expected") {
+                Some(expected) => format!(
+                    r"    {}
                     {}",
-                unexpected, expected
-            );
+                    unexpected,
+                    expected.as_str()
+                ),
+                None => format!("    {}", unexpected),
+            };
 
             let comment = Comment::new(Severity::Error, message);
             let p_comment = PositionedComment::new(Pos { line, column }, comment);
@@ -0,0 +1,248 @@
+//! Rules loaded at runtime from shared libraries.
+//!
+//! A plugin is a `cdylib` crate that depends on `stackup-lint` and declares
+//! its rule with [`declare_plugin!`](../macro.declare_plugin.html):
+//!
+//! ```ignore
+//! use stackup_lint::{declare_plugin, plugin::PluginComment, schema::Schema};
+//!
+//! fn check(schema: &Schema) -> Vec<PluginComment> {
+//!     Vec::new()
+//! }
+//!
+//! declare_plugin!(check);
+//! ```
+//!
+//! The schema and the comments cross the library boundary as JSON, so a
+//! plugin keeps working with any `stackup-lint` built for the same
+//! [`ABI_VERSION`](constant.ABI_VERSION.html).
+
+use crate::interface::{Comment, Pos, PositionedComment, Severity};
+use crate::rules::{Context, Report, Rule};
+use crate::schema::Schema;
+use libloading::{Library, Symbol};
+use serde::{Deserialize, Serialize};
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Bumped whenever the exported functions or the JSON they
+/// exchange change in a way older plugins can't handle
+pub const ABI_VERSION: u32 = 2;
+
+pub const ABI_VERSION_SYMBOL: &[u8] = b"stackup_lint_plugin_abi_version";
+pub const CHECK_SYMBOL: &[u8] = b"stackup_lint_plugin_check";
+pub const FREE_SYMBOL: &[u8] = b"stackup_lint_plugin_free";
+
+type AbiVersionFn = unsafe extern "C" fn() -> u32;
+type CheckFn = unsafe extern "C" fn(*const c_char) -> *mut c_char;
+type FreeFn = unsafe extern "C" fn(*mut c_char);
+
+/// A comment reported by a plugin
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PluginComment {
+    pub line: usize,
+    pub column: usize,
+    pub severity: Severity,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
+
+impl PluginComment {
+    pub fn new(pos: Pos, severity: Severity, message: String) -> Self {
+        Self {
+            line: pos.line,
+            column: pos.column,
+            severity,
+            message,
+            code: None,
+        }
+    }
+
+    pub fn with_code(mut self, code: &str) -> Self {
+        self.code = Some(code.to_string());
+        self
+    }
+}
+
+impl From<PluginComment> for PositionedComment {
+    fn from(c: PluginComment) -> Self {
+        let pos = Pos {
+            line: c.line,
+            column: c.column,
+        };
+        let p_comment = PositionedComment::new(pos, Comment::new(c.severity, c.message));
+        match c.code {
+            Some(ref code) => p_comment.with_code(code),
+            None => p_comment,
+        }
+    }
+}
+
+pub struct Plugin {
+    path: PathBuf,
+    // the functions point into the library, so it's kept loaded
+    // for as long as the plugin is around
+    _library: Library,
+    check: CheckFn,
+    free: FreeFn,
+    // ABI version 1 plugins aren't required to be thread safe, so
+    // files and rules checked concurrently take turns calling one
+    calls: Mutex<()>,
+}
+
+impl Plugin {
+    pub fn load<P: AsRef<Path>>(path: P) -> crate::Result<Self> {
+        let path = path.as_ref();
+        let describe =
+            |e: &dyn std::fmt::Display| format!("Can't load plugin {}: {}", path.display(), e);
+
+        // loading a library runs its initialisers, which is
+        // exactly what asking for a plugin means
+        let library = unsafe { Library::new(path) }.map_err(|e| describe(&e))?;
+        let symbol = |name: &[u8]| {
+            let printable = String::from_utf8_lossy(name).into_owned();
+            move |e: libloading::Error| {
+                describe(&format!(
+                    "{} is missing, is it built with declare_plugin!? ({})",
+                    printable, e
+                ))
+            }
+        };
+
+        let version = unsafe {
+            let abi_version: Symbol<AbiVersionFn> = library
+                .get(ABI_VERSION_SYMBOL)
+                .map_err(symbol(ABI_VERSION_SYMBOL))?;
+            abi_version()
+        };
+        if version != ABI_VERSION {
+            let message = format!(
+                "it was built for plugin ABI version {}, this stackup-lint supports version {}. \
+                 Rebuild the plugin against a matching version of stackup-lint",
+                version, ABI_VERSION
+            );
+            return Err(describe(&message).into());
+        }
+
+        let (check, free) = unsafe {
+            let check: Symbol<CheckFn> = library.get(CHECK_SYMBOL).map_err(symbol(CHECK_SYMBOL))?;
+            let free: Symbol<FreeFn> = library.get(FREE_SYMBOL).map_err(symbol(FREE_SYMBOL))?;
+            (*check, *free)
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            _library: library,
+            check,
+            free,
+            calls: Mutex::new(()),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn run(&self, schema: &Schema) -> Result<Vec<PluginComment>, String> {
+        let input = serde_json::to_string(schema).map_err(|e| e.to_string())?;
+        let input = CString::new(input).map_err(|e| e.to_string())?;
+
+        // held until the output is freed, nothing in between unwinds
+        let _call = self.calls.lock().expect("plugin calls don't panic");
+        let output = unsafe { (self.check)(input.as_ptr()) };
+        if output.is_null() {
+            return Err("the plugin failed to check the schema".to_string());
+        }
+        let comments = unsafe { CStr::from_ptr(output) }
+            .to_str()
+            .map_err(|e| e.to_string())
+            .and_then(|s| serde_json::from_str(s).map_err(|e| e.to_string()));
+        unsafe { (self.free)(output) };
+        comments
+    }
+}
+
+impl Rule for Plugin {
+    fn check(&self, context: &Context, report: &mut Report) {
+        match self.run(context.schema) {
+            Ok(comments) => report
+                .comments
+                .extend(comments.into_iter().map(PositionedComment::from)),
+            Err(e) => {
+                let message = format!("Plugin {} failed: {}", self.path.display(), e);
+                let comment = Comment::new(Severity::Error, message);
+                report
+                    .comments
+                    .push(PositionedComment::new(Pos { line: 1, column: 1 }, comment));
+            }
+        }
+    }
+}
+
+/// Called by the `check` function `declare_plugin!` exports
+///
+/// # Safety
+///
+/// `schema` must be a valid, nul terminated string
+#[doc(hidden)]
+pub unsafe fn export_check(
+    schema: *const c_char,
+    check: fn(&Schema) -> Vec<PluginComment>,
+) -> *mut c_char {
+    // a panic must not unwind into the host
+    let result = panic::catch_unwind(|| {
+        let schema = CStr::from_ptr(schema).to_str().ok()?;
+        let schema: Schema = serde_json::from_str(schema).ok()?;
+        let comments = serde_json::to_string(&check(&schema)).ok()?;
+        CString::new(comments).ok()
+    });
+    match result {
+        Ok(Some(comments)) => comments.into_raw(),
+        _ => std::ptr::null_mut(),
+    }
+}
+
+/// Called by the `free` function `declare_plugin!` exports
+///
+/// # Safety
+///
+/// `comments` must have been returned by `export_check`
+#[doc(hidden)]
+pub unsafe fn export_free(comments: *mut c_char) {
+    if !comments.is_null() {
+        drop(CString::from_raw(comments));
+    }
+}
+
+/// Exports a `fn(&Schema) -> Vec<PluginComment>` as a plugin
+#[macro_export]
+macro_rules! declare_plugin {
+    ($check:path) => {
+        #[no_mangle]
+        pub extern "C" fn stackup_lint_plugin_abi_version() -> u32 {
+            $crate::plugin::ABI_VERSION
+        }
+
+        /// # Safety
+        ///
+        /// Only called by stackup-lint with a valid schema
+        #[no_mangle]
+        pub unsafe extern "C" fn stackup_lint_plugin_check(
+            schema: *const ::std::os::raw::c_char,
+        ) -> *mut ::std::os::raw::c_char {
+            $crate::plugin::export_check(schema, $check)
+        }
+
+        /// # Safety
+        ///
+        /// Only called by stackup-lint with the comments it was given
+        #[no_mangle]
+        pub unsafe extern "C" fn stackup_lint_plugin_free(comments: *mut ::std::os::raw::c_char) {
+            $crate::plugin::export_free(comments)
+        }
+    };
+}
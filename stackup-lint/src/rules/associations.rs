@@ -1,26 +1,69 @@
-use super::ObjectDefn;
+use super::index::Index;
+use crate::inflection::Inflector;
 use crate::interface::{Comment, PositionedComment, Severity};
-use crate::rules::list_of_scalars::extract_field_list_type_name;
-use graphql_parser::{
-    self,
-    query::Type,
-    schema::{Definition, Field},
-};
+use crate::schema::{AssociationKind, Field, Kind, ObjectType, Schema};
 use heck::MixedCase;
 use std::collections::HashMap;
 
 struct FieldWithAssociation<'a> {
     field: &'a Field,
     field_type_name: String,
-    object_defn: &'a ObjectDefn<'a>,
+    object_defn: &'a ObjectType,
 }
 
 impl<'a> FieldWithAssociation<'a> {
-    fn new(field: &'a Field, field_type_name: String, object_defn: &'a ObjectDefn) -> Self {
-        Self {
-            field,
-            field_type_name,
-            object_defn,
+    /// An association is self-referential when an object type
+    /// belongs to another row of the same type, e.g. `parent: Category`
+    fn is_self_referential(&self) -> bool {
+        *self.object_defn.name == self.field_type_name
+    }
+
+    /// The `inverse` argument of the "@belongsTo" directive names the
+    /// field on the associated type, e.g. `@belongsTo(inverse: "editedBooks")`
+    fn explicit_inverse(&self) -> Option<&str> {
+        self.field.explicit_inverse()
+    }
+
+    /// A unique association is a has-one relation, its inverse is a
+    /// single field rather than a list, e.g. `profile: Profile` on `User`
+    fn is_unique(&self) -> bool {
+        self.field.has_directive("unique")
+    }
+
+    /// The name of the field expected on the associated type, or `None`
+    /// when any field of the right shape will do. A field missing its
+    /// "@belongsTo" is expected to have the inverse it would have with it
+    fn inverse_field_name(&self, inflector: &Inflector) -> Option<String> {
+        match self.field.association {
+            Some(ref a) if a.kind == AssociationKind::BelongsTo => a.inverse.clone(),
+            _ if self.is_self_referential() => None,
+            _ if self.is_unique() => Some(self.object_defn.name.to_mixed_case()),
+            _ => Some(inflector.pluralize(&self.object_defn.name).to_mixed_case()),
+        }
+    }
+
+    /// Checks the cardinality of a field on the associated type that
+    /// points back to this association
+    fn is_inverse_shape(&self, field: &Field) -> bool {
+        if self.is_unique() {
+            field.field_type.single_name() == Some(self.object_defn.name.as_str())
+        } else {
+            field.field_type.item_name() == Some(self.object_defn.name.as_str())
+        }
+    }
+
+    /// A field without "@belongsTo" pointing at a type that belongs to this
+    /// one is the inverse of a has-one association rather than an association
+    fn from_field(index: &Index, object_defn: &'a ObjectType, field: &'a Field) -> Option<Self> {
+        let type_name = field.associated_type()?;
+        if field.has_directive("belongsTo") || !index.belongs_to(type_name, &object_defn.name) {
+            Some(Self {
+                field,
+                field_type_name: type_name.to_owned(),
+                object_defn,
+            })
+        } else {
+            None
         }
     }
 }
@@ -28,11 +71,11 @@ impl<'a> FieldWithAssociation<'a> {
 struct FieldWithListType<'a> {
     field: &'a Field,
     field_type_name: String,
-    object_defn: &'a ObjectDefn<'a>,
+    object_defn: &'a ObjectType,
 }
 
 impl<'a> FieldWithListType<'a> {
-    fn new(field: &'a Field, field_type_name: String, object_defn: &'a ObjectDefn) -> Self {
+    fn new(field: &'a Field, field_type_name: String, object_defn: &'a ObjectType) -> Self {
         Self {
             field,
             field_type_name,
@@ -41,45 +84,47 @@ impl<'a> FieldWithListType<'a> {
     }
 }
 
-pub(crate) fn check_associations(defns: &[Definition]) -> Vec<PositionedComment> {
-    let object_defns: Vec<_> = defns.iter().filter_map(ObjectDefn::new).collect();
-    let object_defns_map: HashMap<_, _> = object_defns
-        .iter()
-        .map(|defn| defn.name)
-        .zip(&object_defns)
-        .collect();
-
-    let fields_with_associations: Vec<_> = object_defns
-        .iter()
-        .flat_map(|defn| defn.fields.iter().map(move |f| (f, defn)))
-        .filter_map(|(f, defn)| {
-            extract_field_type_name(&object_defns_map, &f)
-                .map(|f_type_name| FieldWithAssociation::new(f, f_type_name.to_owned(), defn))
-        })
+pub(crate) fn check_associations(
+    schema: &Schema,
+    index: &Index,
+    inflector: &Inflector,
+) -> Vec<PositionedComment> {
+    let fields_with_associations: Vec<_> = schema
+        .fields()
+        .filter_map(|(defn, f)| FieldWithAssociation::from_field(index, defn, f))
         .collect();
 
-    let fields_with_lists_of_object_types: Vec<_> = object_defns
-        .iter()
-        .flat_map(|defn| defn.fields.iter().map(move |f| (f, defn)))
-        .filter_map(|(f, defn)| {
-            extract_field_list_type_name(&f.field_type, false)
-                .filter(|f_type_name| object_defns_map.contains_key(f_type_name))
-                .map(|f_type_name| FieldWithListType::new(f, f_type_name.to_owned(), defn))
+    let fields_with_lists_of_object_types: Vec<_> = schema
+        .fields()
+        .filter(|(_, f)| {
+            f.association
+                .as_ref()
+                .is_some_and(|a| a.kind == AssociationKind::HasMany)
         })
+        .map(|(defn, f)| FieldWithListType::new(f, f.field_type.name().to_owned(), defn))
         .collect();
 
     let mut comments = Vec::new();
+    comments.append(&mut check_belongs_to_placement(schema));
     comments.append(&mut check_belongs_to(&fields_with_associations));
+    comments.append(&mut check_inverse_arguments(&fields_with_associations));
     comments.append(&mut check_fields_for_association(
         &fields_with_associations,
-        &object_defns_map,
+        index,
+        inflector,
+    ));
+    comments.append(&mut check_ambiguous_associations(
+        &fields_with_associations,
+        inflector,
     ));
     comments.append(&mut check_field_name_against_type_name(
         &fields_with_associations,
+        inflector,
     ));
     comments.append(&mut check_list_of_object_types_without_association(
-        &fields_with_associations,
         &fields_with_lists_of_object_types,
+        index,
+        inflector,
     ));
 
     comments
@@ -87,10 +132,15 @@ pub(crate) fn check_associations(defns: &[Definition]) -> Vec<PositionedComment>
 
 fn check_field_name_against_type_name(
     fields_with_associations: &[FieldWithAssociation],
+    inflector: &Inflector,
 ) -> Vec<PositionedComment> {
     fields_with_associations
         .iter()
+        .filter(|f| !f.is_self_referential() && f.explicit_inverse().is_none())
+        .filter(|f| !f.field.has_invalid_inverse())
         .filter(|f| f.field_type_name.to_mixed_case() != f.field.name)
+        // the plural of the expected name is left to the cardinality rule, which can fix it
+        .filter(|f| inflector.singularize(&f.field.name) != f.field_type_name.to_mixed_case())
         .map(|f| {
             let message = format!(
                 r#"Field name should be "{}""#,
@@ -102,10 +152,39 @@ fn check_field_name_against_type_name(
         .collect()
 }
 
+fn check_belongs_to_placement(schema: &Schema) -> Vec<PositionedComment> {
+    schema
+        .fields()
+        .map(|(_, f)| f)
+        .filter(|f| f.has_directive("belongsTo"))
+        .filter_map(|f| {
+            let message = if let Some(type_name) = f.field_type.item_name() {
+                format!(
+                    r#""@belongsTo" cannot be used on a list field, "{}" should be a single "{}""#,
+                    f.name, type_name
+                )
+            } else {
+                let kind = match f.kind {
+                    Kind::Object => return None,
+                    Kind::Scalar => "a scalar",
+                    _ => "not an object type",
+                };
+                format!(
+                    r#""@belongsTo" can only be used on fields with an object type, "{}" is {}"#,
+                    f.field_type.name(),
+                    kind
+                )
+            };
+            let comment = Comment::new(Severity::Error, message);
+            Some(PositionedComment::new(f.position, comment))
+        })
+        .collect()
+}
+
 fn check_belongs_to(fields_with_associations: &[FieldWithAssociation]) -> Vec<PositionedComment> {
     fields_with_associations
         .iter()
-        .filter(|f| !f.field.directives.iter().any(|d| &d.name == "belongsTo"))
+        .filter(|f| !f.field.has_directive("belongsTo"))
         .map(|f| {
             let message = r#"Missing "@belongsTo" directive"#;
             let comment = Comment::new(Severity::Error, message.to_string());
@@ -114,78 +193,193 @@ fn check_belongs_to(fields_with_associations: &[FieldWithAssociation]) -> Vec<Po
         .collect()
 }
 
-fn check_fields_for_association<'a>(
+fn check_inverse_arguments(
     fields_with_associations: &[FieldWithAssociation],
-    object_defns: &HashMap<&String, &'a ObjectDefn>,
 ) -> Vec<PositionedComment> {
     fields_with_associations
         .iter()
-        .filter_map(|f| {
-            extract_field_type_name(object_defns, &f.field)
-                .and_then(|f_type_name| object_defns.get(f_type_name).map(|defn| (f, *defn)))
+        .filter(|f| f.field.has_invalid_inverse())
+        .map(|f| {
+            let message = r#"The "inverse" argument of "@belongsTo" should be a field name string"#;
+            let comment = Comment::new(Severity::Error, message.to_string());
+            PositionedComment::new(f.field.position, comment)
         })
+        .collect()
+}
+
+fn check_fields_for_association(
+    fields_with_associations: &[FieldWithAssociation],
+    index: &Index,
+    inflector: &Inflector,
+) -> Vec<PositionedComment> {
+    fields_with_associations
+        .iter()
+        .filter(|f| !f.field.has_invalid_inverse())
+        .filter_map(|f| index.object(&f.field_type_name).map(|defn| (f, defn)))
         .filter_map(|(f, object_defn)| {
-            let plural_field_name = if f.object_defn.name.ends_with('s') {
-                (f.object_defn.name.clone() + "es").to_mixed_case()
-            } else {
-                (f.object_defn.name.clone() + "s").to_mixed_case()
+            let inverse_field_name = match f.inverse_field_name(inflector) {
+                Some(name) => name,
+                None => return check_self_referential_association(f),
             };
 
-            if !object_defn
+            // Fall back to a field of the right type under a different name,
+            // so the wrong cardinality is reported rather than a missing field
+            let inverse_field = object_defn
                 .fields
                 .iter()
-                .any(|f| f.name == plural_field_name)
-            {
-                let message = format!(
-                    r#"Missing field "{}", due to association on object type {} - {}\n"#,
-                    plural_field_name, f.object_defn.name, f.object_defn.position
-                );
-                let comment = Comment::new(Severity::Error, message.to_string());
-                Some(PositionedComment::new(*object_defn.position, comment))
-            } else {
-                None
+                .find(|other| other.name == inverse_field_name)
+                .or_else(|| {
+                    object_defn.fields.iter().find(|other| {
+                        !other.has_directive("belongsTo")
+                            && other.field_type.name() == f.object_defn.name
+                    })
+                });
+
+            match inverse_field {
+                Some(inverse_field) if f.is_inverse_shape(inverse_field) => {
+                    if inverse_field.name == inverse_field_name {
+                        None
+                    } else {
+                        Some(missing_inverse_field(f, object_defn, &inverse_field_name))
+                    }
+                }
+                Some(inverse_field) => {
+                    let message = if f.is_unique() {
+                        format!(
+                            r#"Field "{}" should be a single "{}", because association "{}" on object type {} is "@unique""#,
+                            inverse_field.name, f.object_defn.name, f.field.name, f.object_defn.name
+                        )
+                    } else if inverse_field.field_type.name() == f.object_defn.name {
+                        format!(
+                            r#"Field "{}" should be a list of "{}", because association "{}" on object type {} isn't "@unique""#,
+                            inverse_field.name, f.object_defn.name, f.field.name, f.object_defn.name
+                        )
+                    } else {
+                        format!(
+                            r#"Field "{}" should be a list of "{}", due to association "{}" on object type {}"#,
+                            inverse_field.name, f.object_defn.name, f.field.name, f.object_defn.name
+                        )
+                    };
+                    let comment = Comment::new(Severity::Error, message);
+                    Some(PositionedComment::new(inverse_field.position, comment))
+                }
+                None => Some(missing_inverse_field(f, object_defn, &inverse_field_name)),
             }
         })
         .collect()
 }
 
-fn check_list_of_object_types_without_association(
+fn missing_inverse_field(
+    f: &FieldWithAssociation,
+    object_defn: &ObjectType,
+    inverse_field_name: &str,
+) -> PositionedComment {
+    let message = format!(
+        r#"Missing field "{}", due to association on object type {} - {}\n"#,
+        inverse_field_name, f.object_defn.name, f.object_defn.position
+    );
+    let comment = Comment::new(Severity::Error, message);
+    PositionedComment::new(object_defn.position, comment)
+}
+
+/// Two associations between the same pair of types can't share an inverse
+/// field, e.g. `author: User!` and `editor: User!` on `Book` would both
+/// expect `books` on `User` unless one of them names its own inverse
+fn check_ambiguous_associations(
     fields_with_associations: &[FieldWithAssociation],
-    fields_with_lists_of_object_types: &[FieldWithListType],
+    inflector: &Inflector,
 ) -> Vec<PositionedComment> {
-    fields_with_lists_of_object_types
+    let mut seen: HashMap<_, &FieldWithAssociation> = HashMap::new();
+
+    fields_with_associations
         .iter()
-        .filter(|&f_list| {
-            !fields_with_associations
-                .iter()
-                .any(|f_assoc| *f_list.object_defn.name == f_assoc.field_type_name)
-        })
-        .map(|f_list| {
+        .filter(|f| !f.field.has_invalid_inverse())
+        .filter_map(|f| {
+            let inverse_field_name = f.inverse_field_name(inflector)?;
+            let key = (&f.field_type_name, inverse_field_name);
+            let first = match seen.get(&key) {
+                Some(first) => first,
+                None => {
+                    seen.insert(key, f);
+                    return None;
+                }
+            };
+
             let message = format!(
-                r#"Missing an association on object type "{0}".
-                Try adding a field with a "@belongsTo" directive on "{0}""#,
-                f_list.field_type_name
+                r#"Fields "{}" and "{}" both use "{}" as their inverse field on object type {}, name a different one with "@belongsTo(inverse: ...)""#,
+                first.field.name, f.field.name, key.1, f.field_type_name
             );
-            let comment = Comment::new(Severity::Warning, message.to_string());
-            PositionedComment::new(f_list.field.position, comment)
+            let comment = Comment::new(Severity::Error, message);
+            Some(PositionedComment::new(f.field.position, comment))
         })
         .collect()
 }
 
-fn extract_field_type_name<'a>(
-    object_defns_map: &HashMap<&String, &'a ObjectDefn>,
-    f: &'a Field,
-) -> Option<&'a String> {
-    match f.field_type {
-        Type::NamedType(ref type_name) if object_defns_map.contains_key(type_name) => {
-            Some(type_name)
-        }
-        Type::NonNullType(ref inner_type) => match **inner_type {
-            Type::NamedType(ref type_name) if object_defns_map.contains_key(type_name) => {
-                Some(type_name)
-            }
-            _ => None,
-        },
-        _ => None,
+/// A self-referential association can't derive its inverse field name from
+/// the type name (`Category` would expect `categories` on itself), so we only
+/// require some field of the same type, e.g. `children: [Category!]!`
+fn check_self_referential_association(f: &FieldWithAssociation) -> Option<PositionedComment> {
+    let has_inverse = f
+        .object_defn
+        .fields
+        .iter()
+        .filter(|other| !other.has_directive("belongsTo"))
+        .any(|other| f.is_inverse_shape(other));
+
+    if has_inverse {
+        None
+    } else {
+        let shape = if f.is_unique() { "field" } else { "list field" };
+        let message = format!(
+            r#"Missing a {0} of type "{1}", due to self-referential association "{2}" on object type {1}"#,
+            shape, f.field_type_name, f.field.name
+        );
+        let comment = Comment::new(Severity::Error, message);
+        Some(PositionedComment::new(f.object_defn.position, comment))
     }
 }
+
+fn check_list_of_object_types_without_association(
+    fields_with_lists_of_object_types: &[FieldWithListType],
+    index: &Index,
+    inflector: &Inflector,
+) -> Vec<PositionedComment> {
+    fields_with_lists_of_object_types
+        .iter()
+        .filter_map(|f_list| {
+            let mut associations = index
+                .associations_to(&f_list.object_defn.name)
+                .filter(|(defn, _)| defn.name == f_list.field_type_name)
+                .filter_map(|(defn, f)| FieldWithAssociation::from_field(index, defn, f));
+
+            let message = match associations.next() {
+                None => format!(
+                    r#"Missing an association on object type "{0}".
+                Try adding a field with a "@belongsTo" directive on "{0}""#,
+                    f_list.field_type_name
+                ),
+                Some(f_assoc) => {
+                    // a list inverse of a unique association is reported
+                    // along with the association
+                    let is_inverse = |f_assoc: &FieldWithAssociation| {
+                        f_assoc.is_unique()
+                            || (!f_assoc.field.has_invalid_inverse()
+                                && f_assoc
+                                    .inverse_field_name(inflector)
+                                    .is_none_or(|name| name == f_list.field.name))
+                    };
+                    if is_inverse(&f_assoc) || associations.any(|f_assoc| is_inverse(&f_assoc)) {
+                        return None;
+                    }
+                    format!(
+                        r#"No association on object type "{0}" uses "{1}" as its inverse field.
+                Try adding "@belongsTo(inverse: "{1}")" to a field on "{0}""#,
+                        f_list.field_type_name, f_list.field.name
+                    )
+                }
+            };
+            let comment = Comment::new(Severity::Warning, message);
+            Some(PositionedComment::new(f_list.field.position, comment))
+        })
+        .collect()
+}
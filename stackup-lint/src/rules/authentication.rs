@@ -0,0 +1,86 @@
+use crate::config::AuthenticationConfig;
+use crate::database::column_name;
+use crate::interface::{Comment, PositionedComment, Severity};
+use crate::schema::{Schema, Type};
+
+/// Columns Stackup adds to the table of the authenticated type
+const GENERATED_COLUMNS: [&str; 5] = [
+    "password",
+    "password_confirmation",
+    "password_digest",
+    "reset_password_token",
+    "reset_password_sent_at",
+];
+
+pub(crate) fn check_authentication(
+    schema: &Schema,
+    config: &AuthenticationConfig,
+) -> Vec<PositionedComment> {
+    let authenticated: Vec<_> = schema
+        .types
+        .iter()
+        .filter(|defn| defn.has_directive("authenticate"))
+        .collect();
+
+    let mut comments = Vec::new();
+
+    if let Some((first, rest)) = authenticated.split_first() {
+        for defn in rest {
+            let message = format!(
+                r#"Only one type can be "@authenticate", "{}" is already authenticated"#,
+                first.name
+            );
+            let comment = Comment::new(Severity::Error, message);
+            let related = format!(r#""{}" is marked "@authenticate" here"#, first.name);
+            comments.push(
+                PositionedComment::new(defn.position, comment)
+                    .with_related(first.position, related),
+            );
+        }
+    }
+
+    let login_field = &config.login_field;
+    let expected = format!("{}: String! @unique", login_field);
+    for defn in &authenticated {
+        match defn.field(login_field) {
+            None => {
+                let message = format!(
+                    r#"Type "{}" is authenticated by "{}", consider adding "{}""#,
+                    defn.name, login_field, expected
+                );
+                let comment = Comment::new(Severity::Error, message);
+                comments.push(PositionedComment::new(defn.position, comment));
+            }
+            Some(f) => {
+                let is_required_string = match f.field_type {
+                    Type::NonNullType(ref inner_type) => {
+                        matches!(**inner_type, Type::NamedType(ref name) if name == "String")
+                    }
+                    _ => false,
+                };
+                if !is_required_string || !f.has_directive("unique") {
+                    let message = format!(
+                        r#"Login field "{}" should be declared as "{}""#,
+                        login_field, expected
+                    );
+                    let comment = Comment::new(Severity::Error, message);
+                    comments.push(PositionedComment::new(f.position, comment));
+                }
+            }
+        }
+
+        for f in &defn.fields {
+            let column = column_name(&f.name);
+            if GENERATED_COLUMNS.contains(&column.as_str()) {
+                let message = format!(
+                    r#"Field "{}" collides with the "{}" column generated for "@authenticate""#,
+                    f.name, column
+                );
+                let comment = Comment::new(Severity::Error, message);
+                comments.push(PositionedComment::new(f.position, comment));
+            }
+        }
+    }
+
+    comments
+}
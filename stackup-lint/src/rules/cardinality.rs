@@ -0,0 +1,64 @@
+use crate::inflection::Inflector;
+use crate::interface::{Comment, Fix, PositionedComment, Severity};
+use crate::schema::{Kind, Schema};
+use std::collections::HashSet;
+
+/// List fields of object types should have plural names and single
+/// object fields singular ones, scalar fields are left alone since
+/// names like `notes: String` are perfectly fine
+pub(crate) fn check_field_cardinality(
+    schema: &Schema,
+    inflector: &Inflector,
+) -> Vec<PositionedComment> {
+    // renaming a field another type names as its inverse would
+    // break that reference, since strings are never rewritten
+    let explicit_inverses: HashSet<_> = schema
+        .fields()
+        .filter_map(|(_, f)| Some((f.field_type.single_name()?, f.explicit_inverse()?)))
+        .collect();
+
+    schema
+        .fields()
+        .filter(|(_, f)| f.kind == Kind::Object)
+        .filter_map(|(defn, f)| {
+            let (message, expected) = if f.field_type.is_list() {
+                if inflector.is_plural(&f.name) {
+                    return None;
+                }
+                let expected = inflector.pluralize(&inflector.singularize(&f.name));
+                let message = format!(
+                    r#"List field "{}" should have a plural name, consider renaming it to "{}""#,
+                    f.name, expected
+                );
+                (message, expected)
+            } else {
+                if inflector.is_singular(&f.name) {
+                    return None;
+                }
+                let expected = inflector.singularize(&f.name);
+                let message = format!(
+                    r#"Field "{}" isn't a list and should have a singular name, consider renaming it to "{}""#,
+                    f.name, expected
+                );
+                (message, expected)
+            };
+            if expected == f.name {
+                return None;
+            }
+
+            let comment = Comment::new(Severity::Warning, message);
+            let p_comment = PositionedComment::new(f.position, comment);
+            let is_taken = defn.fields.iter().any(|other| other.name == expected);
+            let is_inverse = explicit_inverses.contains(&(defn.name.as_str(), f.name.as_str()));
+            // an irregular word, or one the rules leave alone like "cacti",
+            // may not be the form it looks like
+            let is_guess =
+                inflector.is_irregular(&f.name) || inflector.singularize(&f.name) == f.name;
+            if is_taken || is_inverse || is_guess {
+                Some(p_comment)
+            } else {
+                Some(p_comment.with_fix(Fix::rename(f.position, &f.name, &expected)))
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,133 @@
+use super::{Context, Report, Rule};
+use crate::config::{Constraint, CustomRuleConfig, Matcher};
+use crate::interface::{Comment, Pos, PositionedComment};
+use crate::schema::{Field, ObjectType};
+use regex::Regex;
+
+/// A rule declared in the config file, its name patterns
+/// are compiled once rather than for every name they're matched against
+pub(crate) struct CustomRule<'a> {
+    config: &'a CustomRuleConfig,
+    matches: Patterns,
+    require: Patterns,
+    forbid: Patterns,
+}
+
+/// The name patterns of a `match`, `require` or `forbid` table
+struct Patterns {
+    type_name: Option<Regex>,
+    field: Option<Regex>,
+    field_type: Option<Regex>,
+}
+
+impl Patterns {
+    fn from_matcher(matcher: &Matcher) -> Self {
+        Self {
+            type_name: matcher.type_name.as_deref().map(glob),
+            field: matcher.field.as_deref().map(glob),
+            field_type: matcher.field_type.as_deref().map(glob),
+        }
+    }
+
+    fn from_constraint(constraint: &Constraint) -> Self {
+        Self {
+            type_name: None,
+            field: constraint.field.as_deref().map(glob),
+            field_type: constraint.type_name.as_deref().map(glob),
+        }
+    }
+}
+
+impl<'a> CustomRule<'a> {
+    pub fn new(config: &'a CustomRuleConfig) -> Self {
+        Self {
+            config,
+            matches: Patterns::from_matcher(&config.matches),
+            require: Patterns::from_constraint(&config.require),
+            forbid: Patterns::from_constraint(&config.forbid),
+        }
+    }
+
+    fn type_satisfies(&self, defn: &ObjectType) -> bool {
+        let has_field = |pattern: &Regex| defn.fields.iter().any(|f| pattern.is_match(&f.name));
+        let has_type_directive = |name: &String| defn.has_directive(name);
+        let (require, forbid) = (&self.config.require, &self.config.forbid);
+
+        self.require.field.as_ref().is_none_or(has_field)
+            && require.directive.as_ref().is_none_or(has_type_directive)
+            && !self.forbid.field.as_ref().is_some_and(has_field)
+            && !forbid.directive.as_ref().is_some_and(has_type_directive)
+    }
+
+    fn field_satisfies(&self, f: &Field) -> bool {
+        let has_type = |pattern: &Regex| pattern.is_match(f.field_type.name());
+        let has_field_directive = |name: &String| f.has_directive(name);
+        let (require, forbid) = (&self.config.require, &self.config.forbid);
+
+        self.require.field_type.as_ref().is_none_or(has_type)
+            && require.directive.as_ref().is_none_or(has_field_directive)
+            && !self.forbid.field_type.as_ref().is_some_and(has_type)
+            && !forbid.directive.as_ref().is_some_and(has_field_directive)
+    }
+
+    fn report(&self, report: &mut Report, position: Pos, type_name: &str, field_name: &str) {
+        let message = self
+            .config
+            .message
+            .replace("{type}", type_name)
+            .replace("{field}", field_name);
+        let comment = Comment::new(self.config.severity, message);
+        report
+            .comments
+            .push(PositionedComment::new(position, comment).with_code(&self.config.code));
+    }
+}
+
+impl<'a> Rule for CustomRule<'a> {
+    fn check(&self, context: &Context, report: &mut Report) {
+        let matches = &self.config.matches;
+        let types = context.schema.types.iter().filter(|defn| {
+            is_match(&self.matches.type_name, &defn.name)
+                && matches
+                    .directive
+                    .as_ref()
+                    .is_none_or(|name| defn.has_directive(name))
+        });
+
+        for defn in types {
+            if !matches.matches_fields() {
+                if !self.type_satisfies(defn) {
+                    self.report(report, defn.position, &defn.name, "");
+                }
+                continue;
+            }
+
+            let fields = defn.fields.iter().filter(|f| {
+                is_match(&self.matches.field, &f.name)
+                    && is_match(&self.matches.field_type, f.field_type.name())
+                    && matches
+                        .field_directive
+                        .as_ref()
+                        .is_none_or(|name| f.has_directive(name))
+            });
+            for f in fields {
+                if !self.field_satisfies(f) {
+                    self.report(report, f.position, &defn.name, &f.name);
+                }
+            }
+        }
+    }
+}
+
+/// A missing pattern matches everything
+fn is_match(pattern: &Option<Regex>, name: &str) -> bool {
+    pattern
+        .as_ref()
+        .is_none_or(|pattern| pattern.is_match(name))
+}
+
+/// `*Url` matches any name ending in "Url"
+fn glob(pattern: &str) -> Regex {
+    let pattern = regex::escape(pattern).replace(r"\*", ".*");
+    Regex::new(&format!("^{}$", pattern)).expect("escaped patterns are valid")
+}
@@ -0,0 +1,198 @@
+use super::index::Index;
+use crate::interface::{Comment, PositionedComment, Severity};
+use crate::schema::{AssociationKind, Field, Schema};
+
+/// A non-null "@belongsTo" field, the row it points to
+/// has to be inserted before the row that holds it
+struct RequiredAssociation<'a> {
+    field: &'a Field,
+    from: usize,
+    to: usize,
+}
+
+pub(crate) fn check_for_required_association_cycles(
+    schema: &Schema,
+    index: &Index,
+) -> Vec<PositionedComment> {
+    let object_defns = &schema.types;
+
+    let associations: Vec<_> = object_defns
+        .iter()
+        .enumerate()
+        .flat_map(|(from, defn)| defn.fields.iter().map(move |f| (from, f)))
+        .filter(|(_, f)| f.field_type.is_non_null())
+        .filter_map(|(from, field)| match field.association {
+            Some(ref a) if a.kind == AssociationKind::BelongsTo => index
+                .position(&a.target)
+                .map(|to| RequiredAssociation { field, from, to }),
+            _ => None,
+        })
+        .collect();
+
+    let mut edges = vec![Vec::new(); object_defns.len()];
+    for (i, association) in associations.iter().enumerate() {
+        edges[association.from].push(i);
+    }
+
+    // the components that contain a cycle, more than one
+    // type or a type that requires itself
+    let successors: Vec<Vec<usize>> = edges
+        .iter()
+        .map(|edges| edges.iter().map(|&edge| associations[edge].to).collect())
+        .collect();
+    let mut components: Vec<_> = strongly_connected_components(&successors)
+        .into_iter()
+        .filter(|component| component.len() > 1 || successors[component[0]].contains(&component[0]))
+        .collect();
+    components.sort();
+
+    components
+        .into_iter()
+        .filter_map(|component| {
+            let cycle = find_cycle(&component, &edges, &associations)?;
+
+            let path: Vec<_> = cycle
+                .iter()
+                .map(|&i| object_defns[associations[i].from].name.as_str())
+                .chain(std::iter::once(
+                    object_defns[associations[cycle[0]].from].name.as_str(),
+                ))
+                .collect();
+            let message = format!(
+                "Required associations form a cycle {}, no row can be inserted first. \
+                 Make one of these associations nullable",
+                path.join(" -> ")
+            );
+            let first = associations[cycle[0]].field;
+            let comment = Comment::new(Severity::Error, message);
+
+            let participants = associations
+                .iter()
+                .filter(|a| component.contains(&a.from) && component.contains(&a.to));
+            let p_comment = participants.fold(
+                PositionedComment::new(first.position, comment),
+                |p_comment, a| {
+                    let message = format!(
+                        r#""{}" on {} requires {}"#,
+                        a.field.name, object_defns[a.from].name, object_defns[a.to].name
+                    );
+                    p_comment.with_related(a.field.position, message)
+                },
+            );
+            Some(p_comment)
+        })
+        .collect()
+}
+
+/// Tarjan's algorithm, every type ends up in exactly one component.
+/// A component is returned after all the components it can reach
+pub(crate) fn strongly_connected_components(successors: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    struct State {
+        index: usize,
+        indices: Vec<Option<usize>>,
+        low_links: Vec<usize>,
+        stack: Vec<usize>,
+        on_stack: Vec<bool>,
+        components: Vec<Vec<usize>>,
+    }
+
+    fn visit(v: usize, successors: &[Vec<usize>], state: &mut State) {
+        state.indices[v] = Some(state.index);
+        state.low_links[v] = state.index;
+        state.index += 1;
+        state.stack.push(v);
+        state.on_stack[v] = true;
+
+        for &w in &successors[v] {
+            match state.indices[w] {
+                None => {
+                    visit(w, successors, state);
+                    state.low_links[v] = state.low_links[v].min(state.low_links[w]);
+                }
+                Some(index) if state.on_stack[w] => {
+                    state.low_links[v] = state.low_links[v].min(index);
+                }
+                Some(_) => (),
+            }
+        }
+
+        if Some(state.low_links[v]) == state.indices[v] {
+            let mut component = Vec::new();
+            while let Some(w) = state.stack.pop() {
+                state.on_stack[w] = false;
+                component.push(w);
+                if w == v {
+                    break;
+                }
+            }
+            component.sort();
+            state.components.push(component);
+        }
+    }
+
+    let mut state = State {
+        index: 0,
+        indices: vec![None; successors.len()],
+        low_links: vec![0; successors.len()],
+        stack: Vec::new(),
+        on_stack: vec![false; successors.len()],
+        components: Vec::new(),
+    };
+    for v in 0..successors.len() {
+        if state.indices[v].is_none() {
+            visit(v, successors, &mut state);
+        }
+    }
+    state.components
+}
+
+/// Walks the component from its first type until it gets back to it,
+/// returning the associations along the way
+fn find_cycle(
+    component: &[usize],
+    edges: &[Vec<usize>],
+    associations: &[RequiredAssociation],
+) -> Option<Vec<usize>> {
+    fn walk(
+        v: usize,
+        start: usize,
+        component: &[usize],
+        edges: &[Vec<usize>],
+        associations: &[RequiredAssociation],
+        visited: &mut Vec<usize>,
+        path: &mut Vec<usize>,
+    ) -> bool {
+        visited.push(v);
+        for &edge in &edges[v] {
+            let w = associations[edge].to;
+            if !component.contains(&w) {
+                continue;
+            }
+            path.push(edge);
+            if w == start
+                || (!visited.contains(&w)
+                    && walk(w, start, component, edges, associations, visited, path))
+            {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+
+    let start = *component.first()?;
+    let mut path = Vec::new();
+    if walk(
+        start,
+        start,
+        component,
+        edges,
+        associations,
+        &mut Vec::new(),
+        &mut path,
+    ) {
+        Some(path)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,78 @@
+use crate::config::DocumentationConfig;
+use crate::interface::{Comment, Coverage, Pos, PositionedComment, Severity};
+use crate::schema::Schema;
+
+/// Returns the undocumented items when the schema's coverage
+/// is below the minimum, along with the coverage itself
+pub(crate) fn check_documentation(
+    schema: &Schema,
+    config: &DocumentationConfig,
+) -> (Vec<PositionedComment>, Option<Coverage>) {
+    if !config.enabled {
+        return (Vec::new(), None);
+    }
+
+    let mut items: Vec<(String, Pos, &Option<String>)> = Vec::new();
+    for defn in &schema.types {
+        items.push((
+            format!(r#"Type "{}""#, defn.name),
+            defn.position,
+            &defn.description,
+        ));
+        if config.fields {
+            // every type has an "id", there's nothing to say about it
+            items.extend(defn.fields.iter().filter(|f| f.name != "id").map(|f| {
+                let item = format!(r#"Field "{}" on "{}""#, f.name, defn.name);
+                (item, f.position, &f.description)
+            }));
+        }
+    }
+    for defn in &schema.enums {
+        items.push((
+            format!(r#"Enum "{}""#, defn.name),
+            defn.position,
+            &defn.description,
+        ));
+        if config.enum_values {
+            items.extend(defn.values.iter().map(|value| {
+                let item = format!(r#"Enum value "{}" on "{}""#, value.name, defn.name);
+                (item, value.position, &value.description)
+            }));
+        }
+    }
+    // in the order they're declared
+    items.sort_by_key(|(_, pos, _)| (pos.line, pos.column));
+
+    let is_documented =
+        |description: &Option<String>| description.as_ref().is_some_and(|d| !d.trim().is_empty());
+    let coverage = Coverage {
+        documented: items.iter().filter(|(_, _, d)| is_documented(d)).count(),
+        total: items.len(),
+    };
+    if coverage.percentage() >= config.min_coverage {
+        return (Vec::new(), Some(coverage));
+    }
+
+    let mut comments = vec![PositionedComment::new(
+        Pos { line: 1, column: 1 },
+        Comment::new(
+            Severity::Error,
+            format!(
+                "Documentation coverage is {}, below the minimum of {}%",
+                coverage, config.min_coverage
+            ),
+        ),
+    )];
+    comments.extend(
+        items
+            .into_iter()
+            .filter(|(_, _, description)| !is_documented(description))
+            .map(|(item, position, _)| {
+                let message = format!("{} has no description", item);
+                let comment = Comment::new(Severity::Warning, message);
+                PositionedComment::new(position, comment)
+            }),
+    );
+
+    (comments, Some(coverage))
+}
@@ -0,0 +1,69 @@
+use super::index::Index;
+use crate::interface::{Comment, PositionedComment, Severity};
+use crate::schema::{Kind, Schema};
+use heck::ShoutySnakeCase;
+use std::collections::HashMap;
+
+pub(crate) fn check_enums(schema: &Schema, index: &Index) -> Vec<PositionedComment> {
+    let mut comments: Vec<_> = schema
+        .fields()
+        .map(|(_, f)| f)
+        .filter(|f| f.kind == Kind::Enum)
+        .filter_map(|f| {
+            let type_name = f.field_type.item_name()?;
+            let message = format!(
+                r#"Field "{}" is a list of the enum "{}", lists of enums are not supported. You may want an association instead"#,
+                f.name, type_name
+            );
+            let comment = Comment::new(Severity::Warning, message);
+            Some(PositionedComment::new(f.position, comment))
+        })
+        .collect();
+
+    for defn in &schema.enums {
+        let message = match defn.values.as_slice() {
+            [] => Some(format!(r#"Enum "{}" has no values"#, defn.name)),
+            [value] => Some(format!(
+                r#"Enum "{}" only has the value "{}", consider removing it or using a Boolean"#,
+                defn.name, value.name
+            )),
+            _ => None,
+        };
+        if let Some(message) = message {
+            let comment = Comment::new(Severity::Warning, message);
+            comments.push(PositionedComment::new(defn.position, comment));
+        }
+
+        if index.references_to(&defn.name).is_empty() {
+            let message = format!(r#"Enum "{}" is not used by any field"#, defn.name);
+            let comment = Comment::new(Severity::Warning, message);
+            comments.push(PositionedComment::new(defn.position, comment));
+        }
+
+        // "inProgress" and "IN_PROGRESS" end up as the same value
+        // once they're converted to SCREAMING_SNAKE_CASE
+        let mut seen = HashMap::new();
+        for value in &defn.values {
+            let normalized = value.name.to_shouty_snake_case();
+            match seen.get(&normalized) {
+                Some((first_name, first_position)) => {
+                    let message = format!(
+                        r#"Enum value "{}" on "{}" only differs in case from "{}""#,
+                        value.name, defn.name, first_name
+                    );
+                    let comment = Comment::new(Severity::Error, message);
+                    let related = format!(r#""{}" is declared here"#, first_name);
+                    comments.push(
+                        PositionedComment::new(value.position, comment)
+                            .with_related(*first_position, related),
+                    );
+                }
+                None => {
+                    seen.insert(normalized, (&value.name, value.position));
+                }
+            }
+        }
+    }
+
+    comments
+}
@@ -0,0 +1,103 @@
+use super::{index::Index, stored_column};
+use crate::interface::{Comment, Edit, Fix, PositionedComment, Severity};
+use crate::schema::{Field, Schema};
+use heck::{CamelCase, MixedCase};
+
+/// Scalar types a foreign key is usually declared with
+const KEY_TYPES: [&str; 3] = ["ID", "Int", "String"];
+
+/// A scalar field named after an object type, `authorId: ID!` or `author: String`
+struct ForeignKeyField<'a> {
+    field: &'a Field,
+    type_name: String,
+    association_name: String,
+}
+
+pub(crate) fn check_foreign_key_fields(schema: &Schema, index: &Index) -> Vec<PositionedComment> {
+    schema
+        .types
+        .iter()
+        .flat_map(|defn| {
+            // a key stored in the same column as another field, usually the
+            // association it duplicates, is reported as a name collision
+            let columns: Vec<_> = defn
+                .fields
+                .iter()
+                .map(|f| (f, stored_column(f)))
+                .collect();
+            let foreign_keys: Vec<_> = columns
+                .iter()
+                .filter(|(f, column)| {
+                    !columns.iter().any(|(other, other_column)| {
+                        other.name != f.name && other_column.is_some() && other_column == column
+                    })
+                })
+                .filter_map(|(f, _)| ForeignKeyField::new(f, index))
+                .collect();
+
+            foreign_keys
+                .iter()
+                .map(|fk| {
+                    let message = format!(
+                        r#"Field "{}" looks like a foreign key to "{}", consider replacing it with an association"#,
+                        fk.field.name, fk.type_name
+                    );
+                    let comment = Comment::new(Severity::Warning, message);
+                    let p_comment = PositionedComment::new(fk.field.position, comment);
+
+                    // the rewrite is only unambiguous when no other field
+                    // already uses, or would be rewritten to, the same name
+                    let is_ambiguous = defn.fields.iter().any(|f| {
+                        f.name == fk.association_name && f.name != fk.field.name
+                    }) || foreign_keys.iter().any(|other| {
+                        other.association_name == fk.association_name
+                            && other.field.name != fk.field.name
+                    });
+                    if is_ambiguous {
+                        p_comment
+                    } else {
+                        p_comment.with_fix(fk.fix())
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+impl<'a> ForeignKeyField<'a> {
+    fn new(field: &'a Field, index: &Index) -> Option<Self> {
+        let scalar_name = field.field_type.single_name()?;
+        if !KEY_TYPES.contains(&scalar_name) {
+            return None;
+        }
+
+        let base_name = ["Id", "ID", "_id"]
+            .iter()
+            .find_map(|suffix| field.name.strip_suffix(suffix))
+            .unwrap_or(&field.name);
+        let type_name = base_name.to_camel_case();
+        if base_name.is_empty() || index.object(&type_name).is_none() {
+            return None;
+        }
+
+        Some(Self {
+            field,
+            association_name: base_name.to_mixed_case(),
+            type_name,
+        })
+    }
+
+    /// `authorId: ID!` becomes `author: Author! @belongsTo`
+    fn fix(&self) -> Fix {
+        let original = format!("{}: {}", self.field.name, self.field.field_type);
+        let association_type = if self.field.field_type.is_non_null() {
+            format!("{}!", self.type_name)
+        } else {
+            self.type_name.clone()
+        };
+        let replacement = format!("{}: {} @belongsTo", self.association_name, association_type);
+
+        let message = format!(r#"replace "{}" with "{}""#, original, replacement);
+        Fix::new(message).with_edit(Edit::new(self.field.position, &original, &replacement))
+    }
+}
@@ -0,0 +1,63 @@
+use crate::config::GeneratedNamesConfig;
+use crate::inflection::Inflector;
+use crate::interface::{Comment, Pos, PositionedComment, Severity};
+use crate::schema::Schema;
+use std::collections::HashMap;
+
+pub(crate) fn check_generated_names(
+    schema: &Schema,
+    config: &GeneratedNamesConfig,
+    inflector: &Inflector,
+) -> Vec<PositionedComment> {
+    let object_defns = &schema.types;
+
+    // every name Stackup generates, along with the template
+    // and the type it was generated from
+    let mut generated = HashMap::new();
+    for defn in object_defns {
+        let plural = inflector.pluralize(&defn.name);
+        for template in &config.types {
+            let name = template
+                .replace("{Types}", &plural)
+                .replace("{Type}", &defn.name);
+            generated
+                .entry(name)
+                .or_insert((template.as_str(), &defn.name, defn.position));
+        }
+    }
+
+    let type_names = object_defns
+        .iter()
+        .map(|defn| (&defn.name, defn.position))
+        .chain(schema.enums.iter().map(|defn| (&defn.name, defn.position)));
+
+    let mut comments: Vec<_> = type_names
+        .filter_map(|(name, position)| {
+            let (template, source, source_position): &(&str, &String, Pos) = generated.get(name)?;
+            let message = format!(
+                r#"Type "{}" collides with the "{}" type Stackup generates for "{}""#,
+                name, template, source
+            );
+            let comment = Comment::new(Severity::Error, message);
+            let related = format!(r#""{}" is declared here"#, source);
+            Some(PositionedComment::new(position, comment).with_related(*source_position, related))
+        })
+        .collect();
+
+    comments.extend(
+        object_defns
+            .iter()
+            .flat_map(|defn| defn.fields.iter().map(move |f| (defn, f)))
+            .filter(|(_, f)| config.fields.contains(&f.name))
+            .map(|(defn, f)| {
+                let message = format!(
+                    r#"Field "{}" on "{}" collides with the field Stackup generates for every type"#,
+                    f.name, defn.name
+                );
+                let comment = Comment::new(Severity::Error, message);
+                PositionedComment::new(f.position, comment)
+            }),
+    );
+
+    comments
+}
@@ -0,0 +1,112 @@
+use super::index::Index;
+use crate::config::GraphConfig;
+use crate::interface::{Comment, PositionedComment, Severity};
+use crate::schema::{AssociationKind, ObjectType, Schema};
+use std::collections::HashMap;
+
+pub(crate) fn check_association_graph(
+    schema: &Schema,
+    index: &Index,
+    config: &GraphConfig,
+) -> Vec<PositionedComment> {
+    if !config.orphans && !config.disconnected {
+        return Vec::new();
+    }
+
+    let object_defns = &schema.types;
+
+    // every type starts out in a component of its own,
+    // each association merges two of them
+    let mut components: Vec<_> = (0..object_defns.len()).collect();
+    let mut is_associated = vec![false; object_defns.len()];
+    for (from, defn) in object_defns.iter().enumerate() {
+        for f in &defn.fields {
+            let to = f
+                .association
+                .as_ref()
+                .filter(|a| a.kind == AssociationKind::BelongsTo)
+                .and_then(|a| index.position(&a.target));
+            let to = match to {
+                Some(to) => to,
+                None => continue,
+            };
+            if from == to {
+                continue;
+            }
+            is_associated[from] = true;
+            is_associated[to] = true;
+            let (a, b) = (find(&mut components, from), find(&mut components, to));
+            components[a.max(b)] = a.min(b);
+        }
+    }
+
+    let is_allowed =
+        |defn: &ObjectType| config.allow.contains(&defn.name) || defn.has_directive("authenticate");
+
+    let mut comments = Vec::new();
+
+    if config.orphans {
+        for (i, defn) in object_defns.iter().enumerate() {
+            if !is_associated[i] && !is_allowed(defn) {
+                let message = format!(
+                    r#"Type "{}" isn't associated with any other type"#,
+                    defn.name
+                );
+                let comment = Comment::new(Severity::Warning, message);
+                comments.push(PositionedComment::new(defn.position, comment));
+            }
+        }
+    }
+
+    if config.disconnected {
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        let mut group_indices = HashMap::new();
+        for i in (0..object_defns.len()).filter(|&i| is_associated[i]) {
+            let root = find(&mut components, i);
+            let group = *group_indices.entry(root).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(i);
+        }
+
+        // the largest group is the main graph, the first declared one wins a tie
+        let main = (0..groups.len()).rev().max_by_key(|&g| groups[g].len());
+        for (g, group) in groups.iter().enumerate() {
+            if Some(g) == main || group.iter().any(|&i| is_allowed(&object_defns[i])) {
+                continue;
+            }
+
+            let names: Vec<_> = group
+                .iter()
+                .map(|&i| format!(r#""{}""#, object_defns[i].name))
+                .collect();
+            let message = format!(
+                "Types {} aren't connected to the rest of the schema",
+                names.join(", ")
+            );
+            let first = &object_defns[group[0]];
+            let comment = Comment::new(Severity::Warning, message);
+            let p_comment = group[1..].iter().fold(
+                PositionedComment::new(first.position, comment),
+                |p_comment, &i| {
+                    let defn = &object_defns[i];
+                    let related = format!(r#""{}" is declared here"#, defn.name);
+                    p_comment.with_related(defn.position, related)
+                },
+            );
+            comments.push(p_comment);
+        }
+    }
+
+    comments
+}
+
+fn find(components: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+    while components[root] != root {
+        root = components[root];
+    }
+    components[i] = root;
+    root
+}
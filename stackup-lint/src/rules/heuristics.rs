@@ -0,0 +1,116 @@
+use crate::config::HeuristicsConfig;
+use crate::interface::{Comment, Edit, Fix, PositionedComment, Severity};
+use crate::schema::{Field, Kind, Schema};
+use heck::SnakeCase;
+
+const MONEY_WORDS: [&str; 4] = ["price", "amount", "cost", "total"];
+
+/// Leading words that make a Boolean field read as a question
+const PREDICATE_PREFIXES: [&str; 12] = [
+    "is", "has", "can", "should", "was", "will", "did", "does", "allow", "allows", "needs",
+    "requires",
+];
+
+/// Adjective endings, `published`, `visible`, `editable`, `active`
+const ADJECTIVE_SUFFIXES: [&str; 5] = ["ed", "ible", "able", "ive", "ful"];
+
+/// Common adjectives that don't follow any of the endings above
+const ADJECTIVES: [&str; 10] = [
+    "enabled", "open", "public", "private", "primary", "default", "hidden", "valid", "online",
+    "free",
+];
+
+pub(crate) fn check_field_heuristics(
+    schema: &Schema,
+    config: &HeuristicsConfig,
+) -> Vec<PositionedComment> {
+    schema
+        .fields()
+        .map(|(_, f)| f)
+        .filter(|f| !f.field_type.is_list())
+        .filter(|f| f.kind == Kind::Scalar)
+        .filter_map(|f| {
+            let words: Vec<_> = f
+                .name
+                .to_snake_case()
+                .split('_')
+                .map(String::from)
+                .collect();
+            let last_word = words.last()?.as_str();
+            let type_name = f.field_type.name();
+
+            if config.timestamps && words.len() > 1 && last_word == "at" {
+                check_type(f, type_name, "timestamp", "DateTime", "timestamps")
+            } else if config.dates
+                && ((words.len() > 1 && last_word == "on") || last_word == "date")
+            {
+                check_type(f, type_name, "date", "Date", "dates")
+            } else if config.money && MONEY_WORDS.contains(&last_word) && type_name == "Float" {
+                check_type(f, type_name, "money amount", "Decimal", "money")
+            } else if config.booleans && type_name == "Boolean" {
+                check_boolean(f, &words)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn check_type(
+    f: &Field,
+    type_name: &str,
+    kind: &str,
+    expected: &str,
+    code: &str,
+) -> Option<PositionedComment> {
+    if type_name == expected {
+        return None;
+    }
+
+    let message = format!(
+        r#"Field "{}" looks like a {}, consider making it a "{}" instead of a "{}""#,
+        f.name, kind, expected, type_name
+    );
+    let comment = Comment::new(Severity::Warning, message);
+
+    let original = format!("{}: {}", f.name, f.field_type);
+    let replacement = format!("{}: {}", f.name, f.field_type.renamed(expected));
+    let fix = Fix::new(format!(r#"replace "{}" with "{}""#, original, replacement))
+        .with_edit(Edit::new(f.position, &original, &replacement));
+
+    Some(
+        PositionedComment::new(f.position, comment)
+            .with_code(code)
+            .with_fix(fix),
+    )
+}
+
+fn check_boolean(f: &Field, words: &[String]) -> Option<PositionedComment> {
+    let first_word = words.first()?.as_str();
+    let last_word = words.last()?.as_str();
+    let is_predicate = (words.len() > 1 && PREDICATE_PREFIXES.contains(&first_word))
+        || ADJECTIVE_SUFFIXES
+            .iter()
+            .any(|suffix| last_word.ends_with(suffix))
+        || ADJECTIVES.contains(&last_word);
+    if is_predicate {
+        return None;
+    }
+
+    let message = format!(
+        r#"Boolean field "{}" doesn't read as a predicate, consider a name like "is{}" or "has{}""#,
+        f.name,
+        capitalize(&f.name),
+        capitalize(&f.name)
+    );
+    let comment = Comment::new(Severity::Warning, message);
+    Some(PositionedComment::new(f.position, comment).with_code("booleans"))
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -1,19 +1,13 @@
-use super::ObjectDefn;
-use crate::interface::{Comment, PositionedComment, Severity};
-use graphql_parser::{
-    self,
-    query::Type,
-    schema::{Definition, Field},
-    Pos,
-};
+use crate::interface::{Comment, Pos, PositionedComment, Severity};
+use crate::schema::{Field, Schema, Type};
 
-pub(crate) fn check_types_for_id_field(defns: &[Definition]) -> Vec<PositionedComment> {
-    defns
+pub(crate) fn check_types_for_id_field(schema: &Schema) -> Vec<PositionedComment> {
+    schema
+        .types
         .iter()
-        .filter_map(ObjectDefn::new)
         .flat_map(|defn| {
             let id_fields: Vec<_> = defn.fields.iter().filter(|f| f.name == "id").collect();
-            check_id_fields(*defn.position, &defn.name, &id_fields)
+            check_id_fields(defn.position, &defn.name, &id_fields)
         })
         .collect()
 }
@@ -40,20 +34,12 @@ fn check_id_fields(
                 PositionedComment::new(position, comment)
             };
             match id_field.field_type {
-                Type::NamedType(ref type_name) if type_name == "ID" => Some(make_comment()),
-                Type::ListType(_) => Some(make_comment()),
-                Type::NonNullType(ref inner_type) => {
-                    if let Type::NamedType(ref id) = **inner_type {
-                        if id != "ID" {
-                            Some(make_comment())
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                }
-                _ => Some(make_comment()),
+                Type::NonNullType(ref inner_type) => match **inner_type {
+                    Type::NamedType(ref id) if id == "ID" => None,
+                    _ => Some(make_comment()),
+                },
+                // a nullable id, of any type, is reported by the nullability rule
+                _ => None,
             }
         }
         _ => {
@@ -0,0 +1,56 @@
+use super::{index::Index, stored_column};
+use crate::config::IdentifierConfig;
+use crate::database::index_name;
+use crate::interface::{Comment, PositionedComment, Severity};
+use crate::schema::Schema;
+
+pub(crate) fn check_identifier_length(
+    schema: &Schema,
+    index: &Index,
+    config: &IdentifierConfig,
+) -> Vec<PositionedComment> {
+    let mut comments = Vec::new();
+    let mut check = |kind: &str, name: &str, position, derived_kind: &str, derived: &str| {
+        // PostgreSQL counts bytes, not characters
+        if derived.len() > config.max_length {
+            let message = format!(
+                r#"{} "{}" derives the {} "{}", which is {} bytes long. Identifiers longer than {} bytes are truncated"#,
+                kind,
+                name,
+                derived_kind,
+                derived,
+                derived.len(),
+                config.max_length
+            );
+            let comment = Comment::new(Severity::Warning, message);
+            comments.push(PositionedComment::new(position, comment));
+        }
+    };
+
+    for defn in &schema.types {
+        let table = index.table_name(&defn.name);
+        check("Type", &defn.name, defn.position, "table name", table);
+
+        for field in &defn.fields {
+            let column = match stored_column(field) {
+                Some(column) => column,
+                None => continue,
+            };
+            check("Field", &field.name, field.position, "column name", &column);
+
+            // foreign keys and unique columns are indexed
+            if field.has_directive("belongsTo") || field.has_directive("unique") {
+                let index_name = index_name(table, &column);
+                check(
+                    "Field",
+                    &field.name,
+                    field.position,
+                    "index name",
+                    &index_name,
+                );
+            }
+        }
+    }
+
+    comments
+}
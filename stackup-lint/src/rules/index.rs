@@ -0,0 +1,88 @@
+use crate::database::table_name;
+use crate::inflection::Inflector;
+use crate::schema::{Field, ObjectType, Schema};
+use std::collections::{HashMap, HashSet};
+
+/// Lookups the rules share, built in a single pass over the schema
+/// so no rule has to search every type or field for another one
+pub(crate) struct Index<'a> {
+    schema: &'a Schema,
+    /// Where each object type is in `schema.types`
+    positions: HashMap<&'a str, usize>,
+    /// Every object and enum type name
+    type_names: HashSet<&'a str>,
+    /// Every field, by the type it's named after
+    references: HashMap<&'a str, Vec<(&'a ObjectType, &'a Field)>>,
+    table_names: HashMap<&'a str, String>,
+}
+
+impl<'a> Index<'a> {
+    pub fn new(schema: &'a Schema, inflector: &Inflector) -> Self {
+        let mut index = Self {
+            schema,
+            positions: HashMap::with_capacity(schema.types.len()),
+            type_names: HashSet::with_capacity(schema.types.len() + schema.enums.len()),
+            references: HashMap::new(),
+            table_names: HashMap::with_capacity(schema.types.len()),
+        };
+
+        for (i, defn) in schema.types.iter().enumerate() {
+            index.positions.insert(&defn.name, i);
+            index.type_names.insert(&defn.name);
+            index
+                .table_names
+                .insert(&defn.name, table_name(&defn.name, inflector));
+            for f in &defn.fields {
+                index
+                    .references
+                    .entry(f.field_type.name())
+                    .or_default()
+                    .push((defn, f));
+            }
+        }
+        index
+            .type_names
+            .extend(schema.enums.iter().map(|defn| defn.name.as_str()));
+
+        index
+    }
+
+    pub fn object(&self, name: &str) -> Option<&'a ObjectType> {
+        self.position(name).map(|i| &self.schema.types[i])
+    }
+
+    /// Where the object type is in `schema.types`
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.positions.get(name).copied()
+    }
+
+    /// Whether an object or enum type is named `name`
+    pub fn has_type(&self, name: &str) -> bool {
+        self.type_names.contains(name)
+    }
+
+    /// Every field whose type is named `name`, along with the type it's on
+    pub fn references_to(&self, name: &str) -> &[(&'a ObjectType, &'a Field)] {
+        self.references.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// The single object fields of type `name`, `author: Author!`
+    pub fn associations_to<'b>(
+        &'b self,
+        name: &str,
+    ) -> impl Iterator<Item = &'b (&'a ObjectType, &'a Field)> {
+        self.references_to(name)
+            .iter()
+            .filter(|(_, f)| f.associated_type().is_some())
+    }
+
+    /// Whether a "@belongsTo" field on `from` points at `to`
+    pub fn belongs_to(&self, from: &str, to: &str) -> bool {
+        self.associations_to(to)
+            .any(|(defn, f)| defn.name == from && f.has_directive("belongsTo"))
+    }
+
+    pub fn table_name(&self, name: &str) -> &str {
+        &self.table_names[name]
+    }
+}
@@ -0,0 +1,152 @@
+use super::cycles::strongly_connected_components;
+use super::index::Index;
+use crate::config::LimitsConfig;
+use crate::interface::{Comment, Pos, PositionedComment, Severity};
+use crate::schema::{AssociationKind, Kind, Schema};
+
+pub(crate) fn check_limits(
+    schema: &Schema,
+    index: &Index,
+    config: &LimitsConfig,
+) -> Vec<PositionedComment> {
+    let object_defns = &schema.types;
+    let enum_defns = &schema.enums;
+
+    let mut comments = Vec::new();
+    let mut warn = |position: Pos, message: String| {
+        let comment = Comment::new(Severity::Warning, message);
+        comments.push(PositionedComment::new(position, comment));
+    };
+
+    if let Some(max) = config.max_types {
+        let mut positions: Vec<_> = object_defns
+            .iter()
+            .map(|defn| defn.position)
+            .chain(enum_defns.iter().map(|defn| defn.position))
+            .collect();
+        positions.sort_by_key(|pos| (pos.line, pos.column));
+        if let Some(&position) = positions.get(max) {
+            let message = format!(
+                "The schema declares {} types, more than the limit of {}",
+                positions.len(),
+                max
+            );
+            warn(position, message);
+        }
+    }
+
+    for defn in object_defns {
+        let fields = defn.fields.len();
+        if let Some(max) = config.max_fields.filter(|&max| fields > max) {
+            let message = format!(
+                r#"Type "{}" has {} fields, more than the limit of {}"#,
+                defn.name, fields, max
+            );
+            warn(defn.position, message);
+        }
+
+        let associations = defn
+            .fields
+            .iter()
+            .filter(|f| f.kind == Kind::Object)
+            .count();
+        if let Some(max) = config.max_associations.filter(|&max| associations > max) {
+            let message = format!(
+                r#"Type "{}" has {} associations, more than the limit of {}"#,
+                defn.name, associations, max
+            );
+            warn(defn.position, message);
+        }
+    }
+
+    for defn in enum_defns {
+        let values = defn.values.len();
+        if let Some(max) = config.max_enum_values.filter(|&max| values > max) {
+            let message = format!(
+                r#"Enum "{}" has {} values, more than the limit of {}"#,
+                defn.name, values, max
+            );
+            warn(defn.position, message);
+        }
+    }
+
+    if let Some(max) = config.max_association_depth {
+        let edges: Vec<Vec<usize>> = object_defns
+            .iter()
+            .enumerate()
+            .map(|(from, defn)| {
+                defn.fields
+                    .iter()
+                    .filter_map(|f| f.association.as_ref())
+                    .filter(|a| a.kind == AssociationKind::BelongsTo)
+                    .filter_map(|a| index.position(&a.target))
+                    .filter(|&to| to != from)
+                    .collect()
+            })
+            .collect();
+
+        // types that associate with each other in a cycle are one step
+        // of the chain, each of them counted once
+        let components = strongly_connected_components(&edges);
+        let mut component_of = vec![0; object_defns.len()];
+        for (c, component) in components.iter().enumerate() {
+            for &i in component {
+                component_of[i] = c;
+            }
+        }
+
+        // the number of types in the longest chain from each component and
+        // the component it continues with, the components it reaches are
+        // always computed first
+        let mut depths = vec![0; components.len()];
+        let mut next: Vec<Option<usize>> = vec![None; components.len()];
+        let mut is_extended = vec![false; components.len()];
+        for (c, component) in components.iter().enumerate() {
+            for &from in component {
+                for &to in &edges[from] {
+                    let d = component_of[to];
+                    if d != c {
+                        is_extended[d] = true;
+                        if next[c].is_none_or(|n| depths[d] > depths[n]) {
+                            next[c] = Some(d);
+                        }
+                    }
+                }
+            }
+            depths[c] = component.len() + next[c].map_or(0, |n| depths[n]);
+        }
+
+        // only the start of a chain is reported, the types further
+        // along it would just repeat part of the same chain
+        for (i, defn) in object_defns.iter().enumerate() {
+            let c = component_of[i];
+            if depths[c] - 1 <= max || is_extended[c] || components[c][0] != i {
+                continue;
+            }
+
+            let mut path = Vec::new();
+            let mut step = Some(c);
+            while let Some(c) = step {
+                let names: Vec<_> = components[c]
+                    .iter()
+                    .map(|&j| object_defns[j].name.as_str())
+                    .collect();
+                path.push(if names.len() == 1 {
+                    names[0].to_string()
+                } else {
+                    format!("({})", names.join(", "))
+                });
+                step = next[c];
+            }
+            let message = format!(
+                "Association chain {} is {} associations deep, more than the limit of {}",
+                path.join(" -> "),
+                depths[c] - 1,
+                max
+            );
+            warn(defn.position, message);
+        }
+    }
+
+    comments
+}
@@ -1,36 +1,139 @@
-use graphql_parser::{
-    schema::{Definition, Field, ObjectType, TypeDefinition},
-    Pos,
-};
+use crate::config::Config;
+use crate::database::{column_name, foreign_key_column};
+use crate::inflection::Inflector;
+use crate::interface::{PositionedComment, Summary};
+use crate::plugin::Plugin;
+use crate::schema::{Field, Kind, Schema};
+use index::Index;
 
 pub mod associations;
+pub mod authentication;
+pub mod cardinality;
+pub mod custom;
+pub mod cycles;
+pub mod documentation;
+pub mod enums;
+pub mod foreign_keys;
+pub mod generated_names;
+pub mod graph;
+pub mod heuristics;
 pub mod id;
+pub mod identifier_length;
+pub mod index;
+pub mod limits;
 pub mod list_of_scalars;
+pub mod name_collisions;
+pub mod naming;
+pub mod nullability;
+pub mod reserved_words;
+pub mod unique;
 
-/// This is a wrapper around the Definition
-/// enum from graphql_parser
-pub(crate) struct ObjectDefn<'a> {
-    pub fields: &'a Vec<Field>,
-    pub name: &'a String,
-    pub position: &'a Pos,
-}
-
-impl<'a> ObjectDefn<'a> {
-    /// Use the Smart constructor pattern to ensure that we can only
-    /// create an ObjectDefn from values that are object types
-    pub fn new(defn: &'a Definition) -> Option<Self> {
-        match defn {
-            Definition::TypeDefinition(TypeDefinition::Object(ObjectType {
-                fields,
-                name,
-                position,
-                ..
-            })) => Some(Self {
-                fields,
-                name,
-                position,
-            }),
-            _ => None,
-        }
+/// Everything a rule gets to look at
+pub(crate) struct Context<'a> {
+    pub schema: &'a Schema,
+    pub index: &'a Index<'a>,
+    pub config: &'a Config,
+    pub inflector: &'a Inflector,
+}
+
+/// What the rules report back
+#[derive(Default)]
+pub(crate) struct Report {
+    pub comments: Vec<PositionedComment>,
+    pub summary: Summary,
+}
+
+impl Report {
+    /// Adds another rule's report after this one's
+    pub fn merge(mut self, mut other: Report) -> Self {
+        self.comments.append(&mut other.comments);
+        self.summary.documentation = other.summary.documentation.or(self.summary.documentation);
+        self
+    }
+}
+
+/// Built-in and custom rules are run the same way, each one fills
+/// its own report so independent rules can run at the same time
+pub(crate) trait Rule: Send + Sync {
+    fn check(&self, context: &Context, report: &mut Report);
+}
+
+impl<F: Fn(&Context, &mut Report) + Send + Sync> Rule for F {
+    fn check(&self, context: &Context, report: &mut Report) {
+        self(context, report)
+    }
+}
+
+/// Wraps a rule that only reports comments
+fn comments<F>(rule: F) -> Box<dyn Rule>
+where
+    F: Fn(&Context) -> Vec<PositionedComment> + Send + Sync + 'static,
+{
+    Box::new(move |context: &Context, report: &mut Report| {
+        report.comments.append(&mut rule(context))
+    })
+}
+
+/// The built-in rules followed by the custom rules from the config and
+/// the plugins, in the order their comments are reported
+pub(crate) fn all<'a>(config: &'a Config, plugins: &'a [Plugin]) -> Vec<Box<dyn Rule + 'a>> {
+    let mut rules = vec![
+        comments(|c| associations::check_associations(c.schema, c.index, c.inflector)),
+        comments(|c| id::check_types_for_id_field(c.schema)),
+        comments(|c| list_of_scalars::check_for_list_of_scalars(c.schema)),
+        comments(|c| enums::check_enums(c.schema, c.index)),
+        comments(|c| nullability::check_nullability(c.schema, &c.config.nullability)),
+        comments(|c| authentication::check_authentication(c.schema, &c.config.authentication)),
+        comments(|c| unique::check_unique_fields(c.schema, c.config.nullability.unique)),
+        comments(|c| cycles::check_for_required_association_cycles(c.schema, c.index)),
+        comments(|c| graph::check_association_graph(c.schema, c.index, &c.config.graph)),
+        comments(|c| foreign_keys::check_foreign_key_fields(c.schema, c.index)),
+        comments(|c| heuristics::check_field_heuristics(c.schema, &c.config.heuristics)),
+        comments(|c| naming::check_naming_conventions(c.schema, c.index, c.inflector)),
+        comments(|c| cardinality::check_field_cardinality(c.schema, c.inflector)),
+        comments(|c| name_collisions::check_name_collisions(c.schema, c.index)),
+        comments(|c| {
+            generated_names::check_generated_names(c.schema, &c.config.generated_names, c.inflector)
+        }),
+        comments(|c| {
+            identifier_length::check_identifier_length(c.schema, c.index, &c.config.identifiers)
+        }),
+        comments(|c| limits::check_limits(c.schema, c.index, &c.config.limits)),
+        comments(|c| {
+            reserved_words::check_reserved_words(
+                c.schema,
+                c.index,
+                &c.config.reserved_words,
+                c.inflector,
+            )
+        }),
+        Box::new(|c: &Context, report: &mut Report| {
+            let (mut comments, coverage) =
+                documentation::check_documentation(c.schema, &c.config.documentation);
+            report.comments.append(&mut comments);
+            report.summary.documentation = coverage;
+        }),
+    ];
+    rules.extend(
+        config
+            .rules
+            .iter()
+            .map(|rule| Box::new(custom::CustomRule::new(rule)) as Box<dyn Rule>),
+    );
+    rules.extend(plugins.iter().map(|plugin| {
+        Box::new(move |c: &Context, report: &mut Report| plugin.check(c, report)) as Box<dyn Rule>
+    }));
+    rules
+}
+
+/// The column a field is stored in, inverse association fields have
+/// no column of their own, they're derived from the other table
+pub(crate) fn stored_column(field: &Field) -> Option<String> {
+    if field.kind != Kind::Object {
+        Some(column_name(&field.name))
+    } else if field.associated_type().is_some() && field.has_directive("belongsTo") {
+        Some(foreign_key_column(&field.name))
+    } else {
+        None
     }
 }
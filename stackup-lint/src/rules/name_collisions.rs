@@ -0,0 +1,65 @@
+use super::{index::Index, stored_column};
+use crate::interface::{Comment, Pos, PositionedComment, Severity};
+use crate::schema::Schema;
+use std::collections::HashMap;
+
+pub(crate) fn check_name_collisions(schema: &Schema, index: &Index) -> Vec<PositionedComment> {
+    let mut comments = check_collisions(
+        "Type",
+        "table name",
+        schema.types.iter().map(|defn| {
+            (
+                defn.name.as_str(),
+                defn.position,
+                index.table_name(&defn.name).to_string(),
+            )
+        }),
+    );
+
+    for defn in &schema.types {
+        let columns = defn
+            .fields
+            .iter()
+            .filter_map(|f| stored_column(f).map(|column| (f.name.as_str(), f.position, column)));
+        comments.append(&mut check_collisions("Field", "column name", columns));
+    }
+
+    comments
+}
+
+/// Reports every name that derives the same identifier as a name before it
+fn check_collisions<'a>(
+    kind: &str,
+    derived_kind: &str,
+    derived_names: impl Iterator<Item = (&'a str, Pos, String)>,
+) -> Vec<PositionedComment> {
+    let mut seen: HashMap<String, (&str, Pos)> = HashMap::new();
+
+    derived_names
+        .filter_map(|(name, position, derived)| {
+            let (first_name, first_position) = match seen.get(&derived) {
+                Some(first) => *first,
+                None => {
+                    seen.insert(derived, (name, position));
+                    return None;
+                }
+            };
+
+            let message = format!(
+                r#"{0} "{1}" derives the {2} "{3}", which collides with {4} "{5}""#,
+                kind,
+                name,
+                derived_kind,
+                derived,
+                kind.to_lowercase(),
+                first_name
+            );
+            let comment = Comment::new(Severity::Error, message);
+            let related = format!(
+                r#""{}" derives the {} "{}""#,
+                first_name, derived_kind, derived
+            );
+            Some(PositionedComment::new(position, comment).with_related(first_position, related))
+        })
+        .collect()
+}
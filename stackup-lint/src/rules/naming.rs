@@ -0,0 +1,126 @@
+use super::index::Index;
+use crate::inflection::Inflector;
+use crate::interface::{Comment, Edit, Fix, Pos, PositionedComment, Severity};
+use crate::schema::{Field, ObjectType, Schema};
+use heck::{CamelCase, MixedCase, ShoutySnakeCase};
+
+pub(crate) fn check_naming_conventions(
+    schema: &Schema,
+    index: &Index,
+    inflector: &Inflector,
+) -> Vec<PositionedComment> {
+    let mut comments = Vec::new();
+    comments.extend(
+        schema
+            .types
+            .iter()
+            .filter_map(|defn| check_type_name(&defn.name, defn.position, index, inflector)),
+    );
+    comments.extend(
+        schema
+            .enums
+            .iter()
+            .filter_map(|defn| check_type_name(&defn.name, defn.position, index, inflector)),
+    );
+    comments.extend(
+        schema
+            .fields()
+            .filter_map(|(defn, f)| check_field_name(defn, f)),
+    );
+    comments.extend(
+        schema
+            .enums
+            .iter()
+            .flat_map(|defn| defn.values.iter().map(move |value| (defn, value)))
+            .filter_map(|(defn, value)| {
+                let expected = value.name.to_shouty_snake_case();
+                if expected == value.name {
+                    return None;
+                }
+                let message = format!(
+                    r#"Enum value "{}" should be SCREAMING_SNAKE_CASE, consider renaming it to "{}""#,
+                    value.name, expected
+                );
+                let comment = Comment::new(Severity::Warning, message);
+                let p_comment = PositionedComment::new(value.position, comment);
+                // renaming onto an existing value would only trade
+                // this warning for a duplicate value
+                if defn.values.iter().any(|v| v.name == expected) {
+                    return Some(p_comment);
+                }
+                let fix = Fix::rename(value.position, &value.name, &expected);
+                Some(p_comment.with_fix(fix))
+            }),
+    );
+
+    comments
+}
+
+/// Object and enum type names should be singular PascalCase, the fix
+/// renames the type along with every field that refers to it
+fn check_type_name(
+    name: &str,
+    position: Pos,
+    index: &Index,
+    inflector: &Inflector,
+) -> Option<PositionedComment> {
+    let is_pascal_case = name.to_camel_case() == name;
+    let is_singular = inflector.is_singular(name);
+    if is_pascal_case && is_singular {
+        return None;
+    }
+
+    let pascal_case = name.to_camel_case();
+    let expected = inflector.singularize(&pascal_case);
+    if expected == name {
+        return None;
+    }
+    let convention = match (is_pascal_case, is_singular) {
+        (false, false) => "singular and PascalCase",
+        (false, true) => "PascalCase",
+        _ => "singular",
+    };
+    let message = format!(
+        r#"Type name "{}" should be {}, consider renaming it to "{}""#,
+        name, convention, expected
+    );
+    let comment = Comment::new(Severity::Warning, message);
+    let p_comment = PositionedComment::new(position, comment);
+    // renaming onto an existing type would leave two definitions, and
+    // a singular only the rules came up with may not be a word at all
+    let is_guess = expected != pascal_case && !inflector.is_irregular(&pascal_case);
+    if index.has_type(&expected) || is_guess {
+        return Some(p_comment);
+    }
+
+    let fix = index.references_to(name).iter().fold(
+        Fix::rename(position, name, &expected),
+        |fix, (_, f)| {
+            // anchor on the whole `field: Type` so a field sharing
+            // its name with the type isn't renamed instead
+            let original = format!("{}: {}", f.name, f.field_type);
+            let replacement = format!("{}: {}", f.name, f.field_type.renamed(&expected));
+            fix.with_edit(Edit::new(f.position, &original, &replacement))
+        },
+    );
+    Some(p_comment.with_fix(fix))
+}
+
+fn check_field_name(defn: &ObjectType, f: &Field) -> Option<PositionedComment> {
+    let expected = f.name.to_mixed_case();
+    if expected == f.name {
+        return None;
+    }
+
+    let message = format!(
+        r#"Field name "{}" should be camelCase, consider renaming it to "{}""#,
+        f.name, expected
+    );
+    let comment = Comment::new(Severity::Warning, message);
+    let p_comment = PositionedComment::new(f.position, comment);
+    if defn.fields.iter().any(|other| other.name == expected) {
+        return Some(p_comment);
+    }
+    let fix = Fix::rename(f.position, &f.name, &expected);
+    Some(p_comment.with_fix(fix))
+}
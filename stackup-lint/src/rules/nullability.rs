@@ -0,0 +1,85 @@
+use crate::config::{AssociationPolicy, NullabilityConfig};
+use crate::interface::{Comment, PositionedComment, Severity};
+use crate::schema::{Field, Kind, Schema, Type};
+
+pub(crate) fn check_nullability(
+    schema: &Schema,
+    config: &NullabilityConfig,
+) -> Vec<PositionedComment> {
+    schema
+        .fields()
+        .map(|(_, f)| f)
+        .filter_map(|f| {
+            if f.name == "id" {
+                check_id_field(f)
+            } else if let Some(type_name) =
+                f.field_type.item_name().filter(|_| f.kind == Kind::Object)
+            {
+                check_list_field(f, type_name)
+            } else if f.has_directive("belongsTo") {
+                check_belongs_to_field(f, config.associations)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn check_id_field(f: &Field) -> Option<PositionedComment> {
+    match f.field_type {
+        Type::NonNullType(_) => None,
+        _ => {
+            let message = r#"Field "id" can't be nullable, consider making this "id: ID!""#;
+            let comment = Comment::new(Severity::Error, message.to_string());
+            Some(PositionedComment::new(f.position, comment))
+        }
+    }
+}
+
+/// Inverse list fields should always be a non-null list of non-null items,
+/// an association can't have a null row on the other side
+fn check_list_field(f: &Field, type_name: &str) -> Option<PositionedComment> {
+    let is_non_null_list = match f.field_type {
+        Type::NonNullType(ref inner_type) => match **inner_type {
+            Type::ListType(ref item_type) => matches!(**item_type, Type::NonNullType(_)),
+            _ => false,
+        },
+        _ => false,
+    };
+
+    if is_non_null_list {
+        None
+    } else {
+        let message = format!(r#"Consider making this "{}: [{}!]!""#, f.name, type_name);
+        let comment = Comment::new(Severity::Warning, message);
+        Some(PositionedComment::new(f.position, comment))
+    }
+}
+
+fn check_belongs_to_field(f: &Field, policy: AssociationPolicy) -> Option<PositionedComment> {
+    let (is_non_null, type_name) = match f.field_type {
+        Type::NonNullType(ref inner_type) => match **inner_type {
+            Type::NamedType(ref type_name) => (true, type_name),
+            _ => return None,
+        },
+        Type::NamedType(ref type_name) => (false, type_name),
+        Type::ListType(_) => return None,
+    };
+    if f.kind == Kind::Scalar {
+        return None;
+    }
+
+    let message = match policy {
+        AssociationPolicy::Required if !is_non_null => format!(
+            r#"Associations are required, consider making this "{}: {}!""#,
+            f.name, type_name
+        ),
+        AssociationPolicy::Optional if is_non_null => format!(
+            r#"Associations are optional, consider making this "{}: {}""#,
+            f.name, type_name
+        ),
+        _ => return None,
+    };
+    let comment = Comment::new(Severity::Warning, message);
+    Some(PositionedComment::new(f.position, comment))
+}
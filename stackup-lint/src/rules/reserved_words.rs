@@ -0,0 +1,210 @@
+use super::index::Index;
+use crate::config::{ReservedWordTarget, ReservedWordsConfig};
+use crate::database::{column_name, foreign_key_column, model_name};
+use crate::inflection::Inflector;
+use crate::interface::{Comment, Pos, PositionedComment, Severity};
+use crate::schema::{Kind, Schema};
+use heck::MixedCase;
+use lazy_static::lazy_static;
+use std::collections::HashSet;
+
+lazy_static! {
+    static ref POSTGRESQL: HashSet<&'static str> = [
+        "all", "analyse", "analyze", "and", "any", "array", "as", "asc", "asymmetric",
+        "authorization", "binary", "both", "case", "cast", "check", "collate", "collation",
+        "column", "concurrently", "constraint", "create", "cross", "current_catalog",
+        "current_date", "current_role", "current_schema", "current_time", "current_timestamp",
+        "current_user", "default", "deferrable", "desc", "distinct", "do", "else", "end",
+        "except", "false", "fetch", "for", "foreign", "freeze", "from", "full", "grant", "group",
+        "having", "ilike", "in", "initially", "inner", "intersect", "into", "is", "isnull",
+        "join", "lateral", "leading", "left", "like", "limit", "localtime", "localtimestamp",
+        "natural", "not", "notnull", "null", "offset", "on", "only", "or", "order", "outer",
+        "overlaps", "placing", "primary", "references", "returning", "right", "select",
+        "session_user", "similar", "some", "symmetric", "table", "tablesample", "then", "to",
+        "trailing", "true", "union", "unique", "user", "using", "variadic", "verbose", "when",
+        "where", "window", "with",
+    ]
+    .iter()
+    .cloned()
+    .collect();
+
+    /// Ruby keywords, along with the attribute names ActiveRecord
+    /// reserves for itself (`type` is used for single table inheritance)
+    static ref RUBY: HashSet<&'static str> = [
+        "__encoding__", "__line__", "__file__", "alias", "and", "begin", "break", "case",
+        "class", "def", "defined?", "do", "else", "elsif", "end", "ensure", "false", "for", "if",
+        "in", "module", "next", "nil", "not", "or", "redo", "rescue", "retry", "return", "self",
+        "super", "then", "true", "undef", "unless", "until", "when", "while", "yield", "type",
+        "attributes", "errors", "hash", "object_id", "send", "method", "save", "destroy",
+        "reload", "transaction",
+    ]
+    .iter()
+    .cloned()
+    .collect();
+
+    /// JavaScript reserved words, including the ones reserved in strict
+    /// mode, which TypeScript always uses for modules
+    static ref JAVASCRIPT: HashSet<&'static str> = [
+        "await", "break", "case", "catch", "class", "const", "continue", "debugger", "default",
+        "delete", "do", "else", "enum", "export", "extends", "false", "finally", "for",
+        "function", "if", "implements", "import", "in", "instanceof", "interface", "let", "new",
+        "null", "package", "private", "protected", "public", "return", "static", "super",
+        "switch", "this", "throw", "true", "try", "typeof", "var", "void", "while", "with",
+        "yield",
+    ]
+    .iter()
+    .cloned()
+    .collect();
+}
+
+impl ReservedWordTarget {
+    fn is_reserved(self, word: &str) -> bool {
+        match self {
+            Self::PostgreSQL => POSTGRESQL.contains(word.to_lowercase().as_str()),
+            Self::Ruby => RUBY.contains(word.to_lowercase().as_str()),
+            Self::JavaScript => JAVASCRIPT.contains(word),
+        }
+    }
+}
+
+/// A name Stackup derives from a type or field, along
+/// with the targets the name ends up in
+struct DerivedName {
+    kind: &'static str,
+    name: String,
+    targets: &'static [ReservedWordTarget],
+}
+
+impl DerivedName {
+    fn new(kind: &'static str, name: String, targets: &'static [ReservedWordTarget]) -> Self {
+        Self {
+            kind,
+            name,
+            targets,
+        }
+    }
+}
+
+pub(crate) fn check_reserved_words(
+    schema: &Schema,
+    index: &Index,
+    config: &ReservedWordsConfig,
+    inflector: &Inflector,
+) -> Vec<PositionedComment> {
+    use ReservedWordTarget::*;
+
+    let mut comments = Vec::new();
+
+    for defn in &schema.types {
+        let model_name = model_name(&defn.name, inflector);
+        let derived_names = vec![
+            DerivedName::new(
+                "table name",
+                index.table_name(&defn.name).to_string(),
+                &[PostgreSQL],
+            ),
+            // `Order` or `User` are reserved in PostgreSQL even though
+            // their tables, `orders` and `users`, aren't
+            DerivedName::new("model name", model_name.clone(), &[PostgreSQL, Ruby]),
+            DerivedName::new("variable name", model_name.to_mixed_case(), &[JavaScript]),
+        ];
+        comments.extend(check_derived_names(
+            "Type",
+            &defn.name,
+            defn.position,
+            derived_names,
+            config,
+        ));
+
+        for f in defn.fields.iter().filter(|f| f.name != "id") {
+            let derived_names = if f.has_directive("belongsTo") {
+                vec![
+                    DerivedName::new(
+                        "foreign key column",
+                        foreign_key_column(&f.name),
+                        &[PostgreSQL, Ruby],
+                    ),
+                    DerivedName::new("association name", column_name(&f.name), &[Ruby]),
+                    DerivedName::new("property name", f.name.clone(), &[JavaScript]),
+                ]
+            } else if f.kind == Kind::Object {
+                // inverse associations have no column of their own
+                vec![
+                    DerivedName::new("association name", column_name(&f.name), &[Ruby]),
+                    DerivedName::new("property name", f.name.clone(), &[JavaScript]),
+                ]
+            } else {
+                vec![
+                    DerivedName::new("column name", column_name(&f.name), &[PostgreSQL, Ruby]),
+                    DerivedName::new("property name", f.name.clone(), &[JavaScript]),
+                ]
+            };
+            comments.extend(check_derived_names(
+                "Field",
+                &f.name,
+                f.position,
+                derived_names,
+                config,
+            ));
+        }
+    }
+
+    comments
+}
+
+fn check_derived_names(
+    kind: &str,
+    name: &str,
+    position: Pos,
+    derived_names: Vec<DerivedName>,
+    config: &ReservedWordsConfig,
+) -> Vec<PositionedComment> {
+    // the same name can be derived for several targets, e.g. a column
+    // name is also the Ruby attribute name, so report it once
+    let mut collisions: Vec<(DerivedName, Vec<ReservedWordTarget>)> = Vec::new();
+    for derived in derived_names {
+        let targets: Vec<_> = derived
+            .targets
+            .iter()
+            .cloned()
+            .filter(|target| config.targets.contains(target))
+            .filter(|target| target.is_reserved(&derived.name))
+            .collect();
+        match collisions.iter_mut().find(|(d, _)| d.name == derived.name) {
+            Some((_, existing)) => {
+                for target in targets {
+                    if !existing.contains(&target) {
+                        existing.push(target);
+                    }
+                }
+            }
+            None => collisions.push((derived, targets)),
+        }
+    }
+
+    collisions
+        .into_iter()
+        .filter(|(_, targets)| !targets.is_empty())
+        .map(|(derived, targets)| {
+            let targets: Vec<_> = targets.iter().map(|target| target.to_string()).collect();
+            let message = format!(
+                r#"{} "{}" derives the {} "{}", which is a reserved word in {}"#,
+                kind,
+                name,
+                derived.kind,
+                derived.name,
+                join_targets(&targets)
+            );
+            let comment = Comment::new(Severity::Warning, message);
+            PositionedComment::new(position, comment)
+        })
+        .collect()
+}
+
+/// `PostgreSQL`, `PostgreSQL and Ruby`, `PostgreSQL, Ruby and JavaScript`
+fn join_targets(targets: &[String]) -> String {
+    match targets.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        _ => targets.join(""),
+    }
+}
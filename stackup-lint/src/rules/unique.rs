@@ -0,0 +1,52 @@
+use crate::config::UniquePolicy;
+use crate::interface::{Comment, PositionedComment, Severity};
+use crate::schema::{Field, Schema, Type};
+
+pub(crate) fn check_unique_fields(schema: &Schema, policy: UniquePolicy) -> Vec<PositionedComment> {
+    schema
+        .fields()
+        .map(|(_, f)| f)
+        .filter(|f| f.has_directive("unique"))
+        .filter_map(|f| check_unique_field(f, policy))
+        .collect()
+}
+
+fn check_unique_field(f: &Field, policy: UniquePolicy) -> Option<PositionedComment> {
+    let (is_non_null, inner_type) = match f.field_type {
+        Type::NonNullType(ref inner_type) => (true, &**inner_type),
+        ref field_type => (false, field_type),
+    };
+
+    let (severity, message) = match inner_type {
+        Type::ListType(_) => (
+            Severity::Error,
+            format!(r#""@unique" isn't supported on list field "{}""#, f.name),
+        ),
+        Type::NamedType(type_name) if type_name == "File" => (
+            Severity::Error,
+            format!(r#""@unique" isn't supported on File field "{}""#, f.name),
+        ),
+        Type::NamedType(_) if f.name == "id" => (
+            Severity::Error,
+            r#"Field "id" is already unique, remove "@unique""#.to_string(),
+        ),
+        // nullable associations are governed by the association policy,
+        // an optional has-one relation is perfectly valid
+        Type::NamedType(type_name)
+            if policy == UniquePolicy::Required
+                && !is_non_null
+                && !f.has_directive("belongsTo") =>
+        {
+            (
+                Severity::Warning,
+                format!(
+                    r#"Unique field "{0}" is nullable, so several rows can leave it empty. Consider making this "{0}: {1}!""#,
+                    f.name, type_name
+                ),
+            )
+        }
+        _ => return None,
+    };
+    let comment = Comment::new(severity, message);
+    Some(PositionedComment::new(f.position, comment))
+}
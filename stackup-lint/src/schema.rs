@@ -0,0 +1,604 @@
+//! A view of the parsed schema that doesn't depend on graphql-parser,
+//! it's what the built-in rules and plugins look at.
+//!
+//! Besides what's written in the schema, every field knows what
+//! kind of type it has and which association it's part of.
+
+use crate::config::Config;
+use crate::inflection::Inflector;
+use crate::interface::{json::PosDef, Pos};
+use crate::SCALARS;
+use graphql_parser::schema as ast;
+use heck::MixedCase;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Schema {
+    pub types: Vec<ObjectType>,
+    pub enums: Vec<EnumType>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ObjectType {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(with = "PosDef")]
+    pub position: Pos,
+    pub directives: Vec<Directive>,
+    pub fields: Vec<Field>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Field {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(with = "PosDef")]
+    pub position: Pos,
+    /// The type as it's written in the schema, `[Book!]!`
+    pub field_type: Type,
+    pub directives: Vec<Directive>,
+    /// What the named type of `field_type` is
+    #[serde(default)]
+    pub kind: Kind,
+    /// Set on every field with an object type that isn't a list with "@belongsTo"
+    #[serde(default)]
+    pub association: Option<Association>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EnumType {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(with = "PosDef")]
+    pub position: Pos,
+    pub directives: Vec<Directive>,
+    pub values: Vec<EnumValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EnumValue {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(with = "PosDef")]
+    pub position: Pos,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Directive {
+    pub name: String,
+    pub arguments: BTreeMap<String, Value>,
+}
+
+impl Directive {
+    pub fn argument(&self, name: &str) -> Option<&Value> {
+        self.arguments.get(name)
+    }
+}
+
+/// A directive argument, `@column(default: "NONFICTION")`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "lowercase")]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    String(String),
+    Boolean(bool),
+    Null,
+    Enum(String),
+    /// `$name`, named without the "$"
+    Variable(String),
+    List(Vec<Value>),
+    Object(BTreeMap<String, Value>),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// A field type, `[Book!]!` is a non-null list of non-null "Book"s.
+/// It's exchanged with plugins as it's written in the schema
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Type {
+    NamedType(String),
+    ListType(Box<Type>),
+    NonNullType(Box<Type>),
+}
+
+impl Type {
+    /// The type name once the lists and non-nulls are taken off, "Book"
+    pub fn name(&self) -> &str {
+        match self {
+            Self::NamedType(name) => name,
+            Self::ListType(inner_type) | Self::NonNullType(inner_type) => inner_type.name(),
+        }
+    }
+
+    /// The type name of a field that isn't a list, `Book` or `Book!`
+    pub fn single_name(&self) -> Option<&str> {
+        match self {
+            Self::NamedType(name) => Some(name),
+            Self::NonNullType(inner_type) => match **inner_type {
+                Self::NamedType(ref name) => Some(name),
+                _ => None,
+            },
+            Self::ListType(_) => None,
+        }
+    }
+
+    /// The type name of the items of a list field, `[Book!]!` or `[[Book]]`
+    pub fn item_name(&self) -> Option<&str> {
+        match self {
+            Self::NamedType(_) => None,
+            Self::ListType(inner_type) => Some(inner_type.name()),
+            Self::NonNullType(inner_type) => inner_type.item_name(),
+        }
+    }
+
+    pub fn is_list(&self) -> bool {
+        self.item_name().is_some()
+    }
+
+    pub fn is_non_null(&self) -> bool {
+        matches!(self, Self::NonNullType(_))
+    }
+
+    /// The same type with another name, `[Book!]!` becomes `[Novel!]!`
+    pub fn renamed(&self, name: &str) -> Self {
+        match self {
+            Self::NamedType(_) => Self::NamedType(name.to_string()),
+            Self::ListType(inner_type) => Self::ListType(Box::new(inner_type.renamed(name))),
+            Self::NonNullType(inner_type) => Self::NonNullType(Box::new(inner_type.renamed(name))),
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NamedType(name) => write!(f, "{}", name),
+            Self::ListType(inner_type) => write!(f, "[{}]", inner_type),
+            Self::NonNullType(inner_type) => write!(f, "{}!", inner_type),
+        }
+    }
+}
+
+impl FromStr for Type {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(inner) = s.strip_suffix('!') {
+            return inner
+                .parse()
+                .map(|inner_type| Self::NonNullType(Box::new(inner_type)));
+        }
+        if let Some(inner) = s.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            return inner
+                .parse()
+                .map(|inner_type| Self::ListType(Box::new(inner_type)));
+        }
+
+        let is_name = s.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if is_name {
+            Ok(Self::NamedType(s.to_string()))
+        } else {
+            Err(format!("invalid type {:?}", s))
+        }
+    }
+}
+
+impl Serialize for Type {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Type {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// What a type name refers to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    /// One of the scalars Stackup supports
+    Scalar,
+    Enum,
+    Object,
+    /// A type that isn't declared in the schema
+    #[default]
+    Unknown,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Association {
+    pub kind: AssociationKind,
+    /// The associated type
+    pub target: String,
+    /// The field on `target` on the other side of the association.
+    ///
+    /// For a "@belongsTo" field it's the name its inverse is expected to
+    /// have, the `inverse` argument or else derived from the type name, and
+    /// `target` may not have that field. It's `None` when a self-referential
+    /// association doesn't name one or the argument isn't a string.
+    ///
+    /// For any other object field it's the "@belongsTo" field found on
+    /// `target` pointing back at it, `None` when there isn't one
+    pub inverse: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AssociationKind {
+    /// `author: Author! @belongsTo`
+    BelongsTo,
+    /// `profile: Profile`, or a single object field missing its "@belongsTo"
+    HasOne,
+    /// `books: [Book!]!`
+    HasMany,
+}
+
+impl Schema {
+    pub fn new(defns: &[ast::Definition], inflector: &Inflector) -> Self {
+        let object_names: HashSet<_> = defns
+            .iter()
+            .filter_map(|defn| match defn {
+                ast::Definition::TypeDefinition(ast::TypeDefinition::Object(object)) => {
+                    Some(object.name.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+        let enum_names: HashSet<_> = defns
+            .iter()
+            .filter_map(|defn| match defn {
+                ast::Definition::TypeDefinition(ast::TypeDefinition::Enum(enum_type)) => {
+                    Some(enum_type.name.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+        let kind = |name: &str| {
+            if object_names.contains(name) {
+                Kind::Object
+            } else if enum_names.contains(name) {
+                Kind::Enum
+            } else if SCALARS.contains(name) {
+                Kind::Scalar
+            } else {
+                Kind::Unknown
+            }
+        };
+
+        let mut schema = Self::default();
+        for defn in defns {
+            match defn {
+                ast::Definition::TypeDefinition(ast::TypeDefinition::Object(object)) => {
+                    schema.types.push(ObjectType {
+                        name: object.name.clone(),
+                        description: object.description.clone(),
+                        position: object.position,
+                        directives: directives(&object.directives),
+                        fields: object.fields.iter().map(|f| field(f, &kind)).collect(),
+                    })
+                }
+                ast::Definition::TypeDefinition(ast::TypeDefinition::Enum(enum_type)) => {
+                    schema.enums.push(EnumType {
+                        name: enum_type.name.clone(),
+                        description: enum_type.description.clone(),
+                        position: enum_type.position,
+                        directives: directives(&enum_type.directives),
+                        values: enum_type
+                            .values
+                            .iter()
+                            .map(|value| EnumValue {
+                                name: value.name.clone(),
+                                description: value.description.clone(),
+                                position: value.position,
+                            })
+                            .collect(),
+                    })
+                }
+                _ => (),
+            }
+        }
+
+        schema.resolve_belongs_to(inflector);
+        schema.resolve_inverses();
+        schema
+    }
+
+    /// Parses and analyzes a schema, handy for testing a plugin's rule
+    pub fn parse(source: &str, config: &Config) -> crate::Result<Self> {
+        let document = graphql_parser::parse_schema(source).map_err(|e| e.to_string())?;
+        let inflector = Inflector::new(&config.inflections);
+        Ok(Self::new(&document.definitions, &inflector))
+    }
+
+    pub fn object(&self, name: &str) -> Option<&ObjectType> {
+        self.types.iter().find(|t| t.name == name)
+    }
+
+    pub fn enum_type(&self, name: &str) -> Option<&EnumType> {
+        self.enums.iter().find(|e| e.name == name)
+    }
+
+    /// Every field of every object type, along with its type
+    pub fn fields(&self) -> impl Iterator<Item = (&ObjectType, &Field)> {
+        self.types
+            .iter()
+            .flat_map(|t| t.fields.iter().map(move |f| (t, f)))
+    }
+
+    fn resolve_belongs_to(&mut self, inflector: &Inflector) {
+        for object in &mut self.types {
+            let type_name = &object.name;
+            for f in &mut object.fields {
+                if f.kind != Kind::Object || !f.has_directive("belongsTo") {
+                    continue;
+                }
+                let target = match f.field_type.single_name() {
+                    Some(target) => target.to_string(),
+                    None => continue,
+                };
+
+                let inverse = match f.explicit_inverse() {
+                    Some(inverse) => Some(inverse.to_string()),
+                    None if f.has_invalid_inverse() || &target == type_name => None,
+                    None if f.has_directive("unique") => Some(type_name.to_mixed_case()),
+                    None => Some(inflector.pluralize(type_name).to_mixed_case()),
+                };
+                f.association = Some(Association {
+                    kind: AssociationKind::BelongsTo,
+                    target,
+                    inverse,
+                });
+            }
+        }
+    }
+
+    /// The other side of a "@belongsTo" field is any field of the
+    /// associated type it names as its inverse
+    fn resolve_inverses(&mut self) {
+        let objects: HashMap<_, _> = self.types.iter().map(|t| (t.name.as_str(), t)).collect();
+        let associations: Vec<Vec<Option<Association>>> = self
+            .types
+            .iter()
+            .map(|object| {
+                object
+                    .fields
+                    .iter()
+                    .map(|f| {
+                        if f.kind != Kind::Object || f.association.is_some() {
+                            return None;
+                        }
+                        let (kind, target) = match f.field_type.single_name() {
+                            Some(target) => (AssociationKind::HasOne, target),
+                            None if f.has_directive("belongsTo") => return None,
+                            None => (AssociationKind::HasMany, f.field_type.name()),
+                        };
+                        let inverse = objects.get(target).and_then(|target| {
+                            target.fields.iter().find(|other| {
+                                other.association.as_ref().is_some_and(|a| {
+                                    a.kind == AssociationKind::BelongsTo
+                                        && a.target == object.name
+                                        && a.inverse.as_ref().is_none_or(|name| *name == f.name)
+                                        && !other.has_invalid_inverse()
+                                })
+                            })
+                        });
+                        Some(Association {
+                            kind,
+                            target: target.to_string(),
+                            inverse: inverse.map(|other| other.name.clone()),
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        for (object, associations) in self.types.iter_mut().zip(associations) {
+            for (f, association) in object.fields.iter_mut().zip(associations) {
+                if association.is_some() {
+                    f.association = association;
+                }
+            }
+        }
+    }
+}
+
+impl ObjectType {
+    pub fn has_directive(&self, name: &str) -> bool {
+        self.directives.iter().any(|d| d.name == name)
+    }
+
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+}
+
+impl Field {
+    pub fn has_directive(&self, name: &str) -> bool {
+        self.directives.iter().any(|d| d.name == name)
+    }
+
+    pub fn directive(&self, name: &str) -> Option<&Directive> {
+        self.directives.iter().find(|d| d.name == name)
+    }
+
+    /// The inverse field named by `@belongsTo(inverse: "books")`
+    pub fn explicit_inverse(&self) -> Option<&str> {
+        self.directive("belongsTo")
+            .and_then(|d| d.argument("inverse"))
+            .and_then(Value::as_str)
+    }
+
+    /// An `inverse` argument that isn't a string, e.g. `@belongsTo(inverse: 42)`,
+    /// leaves the inverse field unknown rather than falling back to the derived one
+    pub(crate) fn has_invalid_inverse(&self) -> bool {
+        self.directive("belongsTo")
+            .and_then(|d| d.argument("inverse"))
+            .is_some()
+            && self.explicit_inverse().is_none()
+    }
+
+    /// The type a single object field is associated with, a list
+    /// of objects is the other side of an association
+    pub fn associated_type(&self) -> Option<&str> {
+        self.association
+            .as_ref()
+            .filter(|a| a.kind != AssociationKind::HasMany)
+            .map(|a| a.target.as_str())
+    }
+}
+
+fn field(f: &ast::Field, kind: &dyn Fn(&str) -> Kind) -> Field {
+    let field_type = field_type(&f.field_type);
+    Field {
+        name: f.name.clone(),
+        description: f.description.clone(),
+        position: f.position,
+        kind: kind(field_type.name()),
+        field_type,
+        directives: directives(&f.directives),
+        association: None,
+    }
+}
+
+fn field_type(t: &ast::Type) -> Type {
+    match t {
+        ast::Type::NamedType(name) => Type::NamedType(name.clone()),
+        ast::Type::ListType(inner_type) => Type::ListType(Box::new(field_type(inner_type))),
+        ast::Type::NonNullType(inner_type) => Type::NonNullType(Box::new(field_type(inner_type))),
+    }
+}
+
+fn directives(directives: &[ast::Directive]) -> Vec<Directive> {
+    directives
+        .iter()
+        .map(|d| Directive {
+            name: d.name.clone(),
+            arguments: d
+                .arguments
+                .iter()
+                .map(|(name, v)| (name.clone(), value(v)))
+                .collect(),
+        })
+        .collect()
+}
+
+fn value(v: &ast::Value) -> Value {
+    match v {
+        // graphql-parser rejects an int that doesn't fit an i64 as a syntax
+        // error, so there's no value to lose here
+        ast::Value::Int(n) => n.as_i64().map_or(Value::Null, Value::Int),
+        ast::Value::Float(n) => Value::Float(*n),
+        ast::Value::String(s) => Value::String(s.clone()),
+        ast::Value::Boolean(b) => Value::Boolean(*b),
+        ast::Value::Enum(name) => Value::Enum(name.clone()),
+        ast::Value::Variable(name) => Value::Variable(name.clone()),
+        ast::Value::Null => Value::Null,
+        ast::Value::List(values) => Value::List(values.iter().map(value).collect()),
+        ast::Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(name, v)| (name.clone(), value(v)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_type_round_trip() {
+        for s in &["Book", "Book!", "[Book]", "[Book!]!", "[[ID!]]"] {
+            let field_type: Type = s.parse().unwrap();
+            assert_eq!(field_type.to_string(), *s);
+        }
+        // plugins receive it as it's written
+        let field_type: Type = "[Book!]!".parse().unwrap();
+        assert_eq!(serde_json::to_string(&field_type).unwrap(), r#""[Book!]!""#);
+        assert!("[Book".parse::<Type>().is_err());
+        assert!("".parse::<Type>().is_err());
+    }
+
+    #[test]
+    fn test_item_name() {
+        let type_1 = Type::NamedType("String".to_string());
+        let type_2 = Type::NonNullType(Box::new(type_1.clone()));
+        let type_3 = Type::ListType(Box::new(type_1.clone()));
+        let type_4 = Type::NonNullType(Box::new(type_3.clone()));
+        let type_5 = Type::ListType(Box::new(Type::NonNullType(Box::new(Type::NamedType(
+            "ID".to_string(),
+        )))));
+        let type_6 = Type::NonNullType(Box::new(type_5.clone()));
+
+        assert!(type_1.item_name().is_none());
+        assert!(type_2.item_name().is_none());
+        assert_eq!(type_3.item_name(), Some("String"));
+        assert_eq!(type_4.item_name(), Some("String"));
+        assert_eq!(type_5.item_name(), Some("ID"));
+        assert_eq!(type_6.item_name(), Some("ID"));
+    }
+
+    #[test]
+    fn test_type_names() {
+        let field_type: Type = "[Book!]!".parse().unwrap();
+        assert_eq!(field_type.name(), "Book");
+        assert_eq!(field_type.item_name(), Some("Book"));
+        assert_eq!(field_type.single_name(), None);
+        assert!(field_type.is_non_null());
+        assert_eq!(field_type.renamed("Novel").to_string(), "[Novel!]!");
+
+        let field_type: Type = "Author!".parse().unwrap();
+        assert_eq!(field_type.single_name(), Some("Author"));
+        assert!(!field_type.is_list());
+    }
+
+    #[test]
+    fn test_argument_values() {
+        let schema = Schema::parse(
+            r#"
+            type Book {
+              id: ID!
+              genre: Genre @column(default: FICTION, pages: 9007199254740993, name: $name)
+            }
+            "#,
+            &Config::default(),
+        )
+        .unwrap();
+        let directive = &schema.types[0].fields[1].directives[0];
+        assert_eq!(
+            directive.argument("default"),
+            Some(&Value::Enum("FICTION".to_string()))
+        );
+        assert_eq!(
+            directive.argument("pages"),
+            Some(&Value::Int(9_007_199_254_740_993))
+        );
+        assert_eq!(
+            directive.argument("name"),
+            Some(&Value::Variable("name".to_string()))
+        );
+        assert_eq!(
+            serde_json::to_string(directive.argument("name").unwrap()).unwrap(),
+            r#"{"kind":"variable","value":"name"}"#
+        );
+    }
+}
@@ -1,14 +1,35 @@
 use stackup_lint::{
     self,
-    interface::{CheckResult, Comment, Pos, PositionedComment, Severity},
+    config::Config,
+    interface::{
+        CheckResult, Comment, Coverage, Edit, Fix, Pos, PositionedComment, Severity, Summary,
+    },
+    plugin::{Plugin, ABI_VERSION},
+    schema::{Association, AssociationKind, Kind, Schema, Value},
 };
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::path::Path;
+use std::process::Command;
+
+/// `User`, `Order` and `Group` are reserved in PostgreSQL
+fn reserved_model_name(line: usize, type_name: &str) -> PositionedComment {
+    let message = format!(
+        r#"Type "{}" derives the model name "{}", which is a reserved word in PostgreSQL"#,
+        type_name,
+        type_name.to_lowercase()
+    );
+    PositionedComment::new(
+        Pos { line, column: 1 },
+        Comment::new(Severity::Warning, message),
+    )
+}
 
 #[test]
 fn test_check_example() {
     let schema = include_str!("./stackup-example.graphql");
     let check_result = stackup_lint::check(schema);
 
-    let comments = vec![];
+    let comments = vec![reserved_model_name(1, "User")];
     assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
 }
 
@@ -32,7 +53,7 @@ fn test_check() {
             Pos { line: 5, column: 1 },
             Comment::new(
                 Severity::Error,
-                r#"Missing field "quxs", due to association on object type Qux - 10:1\n"#
+                r#"Missing field "quxes", due to association on object type Qux - 10:1\n"#
                     .to_string(),
             ),
         ),
@@ -80,6 +101,25 @@ fn test_check() {
                     .to_string(),
             ),
         ),
+        PositionedComment::new(
+            Pos {
+                line: 27,
+                column: 5,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"List field "tixs" should have a plural name, consider renaming it to "tixes""#
+                    .to_string(),
+            ),
+        )
+        .with_fix(Fix::rename(
+            Pos {
+                line: 27,
+                column: 5,
+            },
+            "tixs",
+            "tixes",
+        )),
     ];
     assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
 }
@@ -103,3 +143,1766 @@ fn test_check_syntax_error() {
     )];
     assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
 }
+
+#[test]
+fn test_check_int_out_of_range() {
+    let schema = r#"type Book {
+  id: ID!
+  pages: Int @column(default: 99999999999999999999)
+}
+"#;
+    let check_result = stackup_lint::check(schema);
+
+    let comments = vec![PositionedComment::new(
+        Pos {
+            line: 3,
+            column: 31,
+        },
+        Comment::new(
+            Severity::Error,
+            "    number too large to fit in target type".to_string(),
+        ),
+    )];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+}
+
+#[test]
+fn test_json_output() {
+    // a file is reported as the list of its comments
+    let schema = include_str!("./syntax-error.graphql");
+    assert_eq!(
+        stackup_lint::check(schema).to_json().unwrap(),
+        r#"[
+  {
+    "start_pos": {
+      "line": 2,
+      "column": 12
+    },
+    "end_pos": {
+      "line": 2,
+      "column": 12
+    },
+    "severity": "Error",
+    "message": "    Unexpected `![Punctuator]`\n                    Expected `}`"
+  }
+]"#
+    );
+
+    // several files are a list of objects, with a "summary" only when asked
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+    let output = Command::new(env!("CARGO_BIN_EXE_stackup-lint"))
+        .current_dir(&dir)
+        .args(["-f", "json", "plugin.graphql", "plugin.graphql"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        r#"[
+  {
+    "file": "plugin.graphql",
+    "comments": []
+  }
+]
+"#
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_stackup-lint"))
+        .current_dir(&dir)
+        .args([
+            "-f",
+            "json",
+            "--summary",
+            "plugin.graphql",
+            "plugin.graphql",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        r#"[
+  {
+    "file": "plugin.graphql",
+    "comments": [],
+    "summary": {}
+  }
+]
+"#
+    );
+
+    // a single file has the same shape with --summary, even when no rule
+    // reported one
+    let output = Command::new(env!("CARGO_BIN_EXE_stackup-lint"))
+        .current_dir(&dir)
+        .args(["-f", "json", "--summary", "plugin.graphql"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        r#"{
+  "comments": [],
+  "summary": {}
+}
+"#
+    );
+}
+
+#[test]
+fn test_check_belongs_to_placement() {
+    let schema = include_str!("./belongs-to-placement.graphql");
+    let check_result = stackup_lint::check(schema);
+
+    let comments = vec![
+        PositionedComment::new(
+            Pos {
+                line: 12,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Error,
+                r#""@belongsTo" can only be used on fields with an object type, "String" is a scalar"#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 13,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Error,
+                r#""@belongsTo" can only be used on fields with an object type, "Genre" is not an object type"#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 14,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Error,
+                r#""@belongsTo" cannot be used on a list field, "authors" should be a single "Author""#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 23,
+                column: 1,
+            },
+            Comment::new(
+                Severity::Error,
+                r#"Missing a list field of type "Tag", due to self-referential association "parent" on object type Tag"#
+                    .to_string(),
+            ),
+        ),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+}
+
+#[test]
+fn test_check_with_config() {
+    let schema = include_str!("./inflections.graphql");
+    let config = Config::from_toml(
+        r#"
+        [inflections]
+        irregular = { cactus = "cacti" }
+        "#,
+    )
+    .unwrap();
+
+    let check_result = stackup_lint::check_with_config(schema, &config);
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), vec![]));
+
+    let check_result = stackup_lint::check(schema);
+    let comments = vec![
+        PositionedComment::new(
+            Pos { line: 1, column: 1 },
+            Comment::new(
+                Severity::Error,
+                r#"Missing field "cactuses", due to association on object type Cactus - 6:1\n"#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos { line: 3, column: 3 },
+            Comment::new(
+                Severity::Warning,
+                r#"No association on object type "Cactus" uses "cacti" as its inverse field.
+                Try adding "@belongsTo(inverse: "cacti")" to a field on "Cactus""#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos { line: 3, column: 3 },
+            Comment::new(
+                Severity::Warning,
+                r#"List field "cacti" should have a plural name, consider renaming it to "cactis""#
+                    .to_string(),
+            ),
+        ),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+}
+
+#[test]
+fn test_check_inverse_associations() {
+    let schema = include_str!("./inverse-associations.graphql");
+    let check_result = stackup_lint::check(schema);
+
+    let comments = vec![
+        PositionedComment::new(
+            Pos { line: 15, column: 3 },
+            Comment::new(Severity::Error, r#"The "inverse" argument of "@belongsTo" should be a field name string"#.to_string()),
+        ),
+        PositionedComment::new(
+            Pos { line: 1, column: 1 },
+            Comment::new(Severity::Error, r#"Missing field "translatedBooks", due to association on object type Book - 8:1\n"#.to_string()),
+        ),
+        PositionedComment::new(
+            Pos { line: 20, column: 3 },
+            Comment::new(Severity::Error, r#"Field "catalog" should be a list of "Book", due to association "publisher" on object type Book"#.to_string()),
+        ),
+        PositionedComment::new(
+            Pos { line: 13, column: 3 },
+            Comment::new(Severity::Error, r#"Fields "author" and "user" both use "books" as their inverse field on object type User, name a different one with "@belongsTo(inverse: ...)""#.to_string()),
+        ),
+        PositionedComment::new(
+            Pos { line: 5, column: 3 },
+            Comment::new(Severity::Warning, r#"No association on object type "Book" uses "reviewedBooks" as its inverse field.
+                Try adding "@belongsTo(inverse: "reviewedBooks")" to a field on "Book""#.to_string()),
+        ),
+        reserved_model_name(1, "User"),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+}
+
+#[test]
+fn test_check_has_one_associations() {
+    let schema = include_str!("./has-one-associations.graphql");
+    let check_result = stackup_lint::check(schema);
+
+    let comments = vec![
+        PositionedComment::new(
+            Pos { line: 4, column: 3 },
+            Comment::new(
+                Severity::Error,
+                r#"Field "avatars" should be a single "Avatar", because association "user" on object type Avatar is "@unique""#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos { line: 5, column: 3 },
+            Comment::new(
+                Severity::Error,
+                r#"Field "post" should be a list of "Post", because association "user" on object type Post isn't "@unique""#
+                    .to_string(),
+            ),
+        ),
+        reserved_model_name(1, "User"),
+        PositionedComment::new(
+            Pos {
+                line: 26,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Field "next" derives the association name "next", which is a reserved word in Ruby"#
+                    .to_string(),
+            ),
+        ),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+}
+
+#[test]
+fn test_check_nullability() {
+    let schema = include_str!("./nullability.graphql");
+    let config = Config::from_toml(
+        r#"
+        [nullability]
+        associations = "required"
+        "#,
+    )
+    .unwrap();
+    let check_result = stackup_lint::check_with_config(schema, &config);
+
+    let comments = vec![
+        PositionedComment::new(
+            Pos { line: 2, column: 3 },
+            Comment::new(
+                Severity::Error,
+                r#"Field "id" can't be nullable, consider making this "id: ID!""#.to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos { line: 3, column: 3 },
+            Comment::new(
+                Severity::Warning,
+                r#"Consider making this "books: [Book!]!""#.to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 14,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Associations are required, consider making this "author: Author!""#.to_string(),
+            ),
+        ),
+        // reported once, not again by the id type check
+        PositionedComment::new(
+            Pos {
+                line: 18,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Error,
+                r#"Field "id" can't be nullable, consider making this "id: ID!""#.to_string(),
+            ),
+        ),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+}
+
+#[test]
+fn test_check_association_cycles() {
+    let schema = include_str!("./association-cycles.graphql");
+    let check_result = stackup_lint::check(schema);
+
+    let comments = vec![
+        PositionedComment::new(
+            Pos { line: 3, column: 3 },
+            Comment::new(
+                Severity::Error,
+                "Required associations form a cycle Company -> Employee -> Company, \
+                 no row can be inserted first. Make one of these associations nullable"
+                    .to_string(),
+            ),
+        )
+        .with_related(
+            Pos { line: 3, column: 3 },
+            r#""ceo" on Company requires Employee"#.to_string(),
+        )
+        .with_related(
+            Pos { line: 9, column: 3 },
+            r#""company" on Employee requires Company"#.to_string(),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 15,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Error,
+                "Required associations form a cycle Category -> Category, \
+                 no row can be inserted first. Make one of these associations nullable"
+                    .to_string(),
+            ),
+        )
+        .with_related(
+            Pos {
+                line: 15,
+                column: 3,
+            },
+            r#""parent" on Category requires Category"#.to_string(),
+        ),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+}
+
+#[test]
+fn test_check_naming() {
+    let schema = include_str!("./naming.graphql");
+    let check_result = stackup_lint::check(schema);
+
+    let comments = vec![
+        PositionedComment::new(
+            Pos { line: 1, column: 1 },
+            Comment::new(
+                Severity::Error,
+                r#"Missing field "bookses", due to association on object type Books - 7:1\n"#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos { line: 4, column: 3 },
+            Comment::new(
+                Severity::Warning,
+                r#"No association on object type "Books" uses "books" as its inverse field.
+                Try adding "@belongsTo(inverse: "books")" to a field on "Books""#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos { line: 1, column: 1 },
+            Comment::new(
+                Severity::Warning,
+                r#"Type name "book_author" should be PascalCase, consider renaming it to "BookAuthor""#
+                    .to_string(),
+            ),
+        )
+        .with_fix(
+            Fix::rename(Pos { line: 1, column: 1 }, "book_author", "BookAuthor").with_edit(
+                Edit::new(
+                    Pos {
+                        line: 10,
+                        column: 3,
+                    },
+                    "bookAuthor: book_author!",
+                    "bookAuthor: BookAuthor!",
+                ),
+            ),
+        ),
+        // "Book" only comes from the rules, so it isn't fixed
+        PositionedComment::new(
+            Pos { line: 7, column: 1 },
+            Comment::new(
+                Severity::Warning,
+                r#"Type name "Books" should be singular, consider renaming it to "Book""#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 13,
+                column: 1,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Type name "genre" should be PascalCase, consider renaming it to "Genre""#
+                    .to_string(),
+            ),
+        )
+        .with_fix(
+            Fix::rename(
+                Pos {
+                    line: 13,
+                    column: 1,
+                },
+                "genre",
+                "Genre",
+            )
+            .with_edit(Edit::new(
+                Pos { line: 9, column: 3 },
+                "genre: genre!",
+                "genre: Genre!",
+            )),
+        ),
+        PositionedComment::new(
+            Pos { line: 3, column: 3 },
+            Comment::new(
+                Severity::Warning,
+                r#"Field name "first_name" should be camelCase, consider renaming it to "firstName""#
+                    .to_string(),
+            ),
+        )
+        .with_fix(Fix::rename(
+            Pos { line: 3, column: 3 },
+            "first_name",
+            "firstName",
+        )),
+        PositionedComment::new(
+            Pos {
+                line: 15,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Enum value "nonFiction" should be SCREAMING_SNAKE_CASE, consider renaming it to "NON_FICTION""#
+                    .to_string(),
+            ),
+        )
+        .with_fix(Fix::rename(
+            Pos {
+                line: 15,
+                column: 3,
+            },
+            "nonFiction",
+            "NON_FICTION",
+        )),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+
+    let fixed = check_result.apply_fixes();
+    assert_eq!(
+        fixed,
+        schema
+            .replace("book_author", "BookAuthor")
+            .replace("first_name", "firstName")
+            .replace("genre!", "Genre!")
+            .replace("enum genre", "enum Genre")
+            .replace("nonFiction", "NON_FICTION")
+    );
+}
+
+#[test]
+fn test_check_reserved_words() {
+    let schema = include_str!("./reserved-words.graphql");
+    let check_result = stackup_lint::check(schema);
+
+    let comments = vec![
+        reserved_model_name(1, "Order"),
+        PositionedComment::new(
+            Pos { line: 3, column: 3 },
+            Comment::new(Severity::Warning, r#"Field "end" derives the column name "end", which is a reserved word in PostgreSQL and Ruby"#.to_string()),
+        ),
+        PositionedComment::new(
+            Pos { line: 4, column: 3 },
+            Comment::new(Severity::Warning, r#"Field "class" derives the column name "class", which is a reserved word in Ruby and JavaScript"#.to_string()),
+        ),
+        PositionedComment::new(
+            Pos { line: 5, column: 3 },
+            Comment::new(Severity::Warning, r#"Field "default" derives the column name "default", which is a reserved word in PostgreSQL and JavaScript"#.to_string()),
+        ),
+        PositionedComment::new(
+            Pos { line: 6, column: 3 },
+            Comment::new(Severity::Warning, r#"Field "type" derives the column name "type", which is a reserved word in Ruby"#.to_string()),
+        ),
+        PositionedComment::new(
+            Pos { line: 9, column: 1 },
+            Comment::new(Severity::Warning, r#"Type "Do" derives the model name "do", which is a reserved word in PostgreSQL, Ruby and JavaScript"#.to_string()),
+        ),
+        // their tables, "users" and "groups", aren't reserved but the models are
+        reserved_model_name(14, "User"),
+        reserved_model_name(18, "Group"),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+
+    let config = Config::from_toml(
+        r#"
+        [reserved_words]
+        targets = ["postgresql"]
+        "#,
+    )
+    .unwrap();
+    let check_result = stackup_lint::check_with_config(schema, &config);
+
+    let comments = vec![
+        reserved_model_name(1, "Order"),
+        PositionedComment::new(
+            Pos { line: 3, column: 3 },
+            Comment::new(Severity::Warning, r#"Field "end" derives the column name "end", which is a reserved word in PostgreSQL"#.to_string()),
+        ),
+        PositionedComment::new(
+            Pos { line: 5, column: 3 },
+            Comment::new(Severity::Warning, r#"Field "default" derives the column name "default", which is a reserved word in PostgreSQL"#.to_string()),
+        ),
+        PositionedComment::new(
+            Pos { line: 9, column: 1 },
+            Comment::new(Severity::Warning, r#"Type "Do" derives the model name "do", which is a reserved word in PostgreSQL"#.to_string()),
+        ),
+        reserved_model_name(14, "User"),
+        reserved_model_name(18, "Group"),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+}
+
+#[test]
+fn test_check_name_collisions() {
+    let schema = include_str!("./name-collisions.graphql");
+    let check_result = stackup_lint::check(schema);
+
+    let comments = vec![
+        PositionedComment::new(
+            Pos { line: 8, column: 1 },
+            Comment::new(
+                Severity::Warning,
+                r#"Type name "People" should be singular, consider renaming it to "Person""#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos { line: 4, column: 3 },
+            Comment::new(
+                Severity::Warning,
+                r#"Field name "postal_code" should be camelCase, consider renaming it to "postalCode""#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos { line: 8, column: 1 },
+            Comment::new(
+                Severity::Error,
+                r#"Type "People" derives the table name "people", which collides with type "Person""#
+                    .to_string(),
+            ),
+        )
+        .with_related(
+            Pos { line: 1, column: 1 },
+            r#""Person" derives the table name "people""#.to_string(),
+        ),
+        PositionedComment::new(
+            Pos { line: 4, column: 3 },
+            Comment::new(
+                Severity::Error,
+                r#"Field "postal_code" derives the column name "postal_code", which collides with field "postalCode""#
+                    .to_string(),
+            ),
+        )
+        .with_related(
+            Pos { line: 3, column: 3 },
+            r#""postalCode" derives the column name "postal_code""#.to_string(),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 15,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Error,
+                r#"Field "author" derives the column name "author_id", which collides with field "authorId""#
+                    .to_string(),
+            ),
+        )
+        .with_related(
+            Pos {
+                line: 14,
+                column: 3,
+            },
+            r#""authorId" derives the column name "author_id""#.to_string(),
+        ),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+
+    // both renames would collide with a name already in the schema
+    assert_eq!(check_result.apply_fixes(), schema);
+}
+
+#[test]
+fn test_check_identifier_length() {
+    let schema = include_str!("./identifier-length.graphql");
+    let check_result = stackup_lint::check(schema);
+
+    let comments = vec![
+        PositionedComment::new(
+            Pos { line: 1, column: 1 },
+            Comment::new(
+                Severity::Warning,
+                r#"Type "RegionalDistributionCenterInventoryReconciliationSummaryReport" derives the table name "regional_distribution_center_inventory_reconciliation_summary_reports", which is 69 bytes long. Identifiers longer than 63 bytes are truncated"#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos { line: 9, column: 3 },
+            Comment::new(
+                Severity::Warning,
+                r#"Field "externalWarehouseManagementSystemIdentifierReference" derives the index name "index_reconciliation_lines_on_external_warehouse_management_system_identifier_reference", which is 87 bytes long. Identifiers longer than 63 bytes are truncated"#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 10,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Field "regionalDistributionCenterInventoryReconciliationSummaryReport" derives the column name "regional_distribution_center_inventory_reconciliation_summary_report_id", which is 71 bytes long. Identifiers longer than 63 bytes are truncated"#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 10,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Field "regionalDistributionCenterInventoryReconciliationSummaryReport" derives the index name "index_reconciliation_lines_on_regional_distribution_center_inventory_reconciliation_summary_report_id", which is 101 bytes long. Identifiers longer than 63 bytes are truncated"#
+                    .to_string(),
+            ),
+        ),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+
+    let config = Config::from_toml(
+        r#"
+        [identifiers]
+        max_length = 100
+        "#,
+    )
+    .unwrap();
+    let check_result = stackup_lint::check_with_config(schema, &config);
+
+    let comments = vec![PositionedComment::new(
+        Pos {
+            line: 10,
+            column: 3,
+        },
+        Comment::new(
+            Severity::Warning,
+            r#"Field "regionalDistributionCenterInventoryReconciliationSummaryReport" derives the index name "index_reconciliation_lines_on_regional_distribution_center_inventory_reconciliation_summary_report_id", which is 101 bytes long. Identifiers longer than 100 bytes are truncated"#
+                .to_string(),
+        ),
+    )];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+}
+
+#[test]
+fn test_check_foreign_keys() {
+    let schema = include_str!("./foreign-keys.graphql");
+    let check_result = stackup_lint::check(schema);
+
+    let comments = vec![
+        PositionedComment::new(
+            Pos { line: 3, column: 3 },
+            Comment::new(
+                Severity::Warning,
+                r#"Missing an association on object type "Book".
+                Try adding a field with a "@belongsTo" directive on "Book""#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 12,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Field "authorId" looks like a foreign key to "Author", consider replacing it with an association"#
+                    .to_string(),
+            ),
+        )
+        .with_fix(
+            Fix::new(r#"replace "authorId: ID!" with "author: Author! @belongsTo""#.to_string())
+                .with_edit(Edit::new(
+                    Pos {
+                        line: 12,
+                        column: 3,
+                    },
+                    "authorId: ID!",
+                    "author: Author! @belongsTo",
+                )),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 13,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Field "publisher" looks like a foreign key to "Publisher", consider replacing it with an association"#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 14,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Field "publisherId" looks like a foreign key to "Publisher", consider replacing it with an association"#
+                    .to_string(),
+            ),
+        ),
+        // reported as a collision with its association rather than as a foreign key
+        PositionedComment::new(
+            Pos {
+                line: 26,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Error,
+                r#"Field "editorId" derives the column name "editor_id", which collides with field "editor""#
+                    .to_string(),
+            ),
+        )
+        .with_related(
+            Pos {
+                line: 25,
+                column: 3,
+            },
+            r#""editor" derives the column name "editor_id""#.to_string(),
+        ),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+
+    let fixed = check_result.apply_fixes();
+    assert!(fixed.contains("  author: Author! @belongsTo\n  publisher: String\n"));
+}
+
+#[test]
+fn test_check_enums() {
+    let schema = include_str!("./enums.graphql");
+    let check_result = stackup_lint::check(schema);
+
+    let comments = vec![
+        PositionedComment::new(
+            Pos { line: 3, column: 3 },
+            Comment::new(
+                Severity::Warning,
+                r#"Field "genres" is a list of the enum "Genre", lists of enums are not supported. You may want an association instead"#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 16,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Error,
+                r#"Enum value "inProgress" on "Status" only differs in case from "IN_PROGRESS""#
+                    .to_string(),
+            ),
+        )
+        .with_related(
+            Pos {
+                line: 15,
+                column: 3,
+            },
+            r#""IN_PROGRESS" is declared here"#.to_string(),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 19,
+                column: 1,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Enum "Format" only has the value "HARDCOVER", consider removing it or using a Boolean"#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 23,
+                column: 1,
+            },
+            Comment::new(Severity::Warning, r#"Enum "Archived" has no values"#.to_string()),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 23,
+                column: 1,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Enum "Archived" is not used by any field"#.to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 16,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Enum value "inProgress" should be SCREAMING_SNAKE_CASE, consider renaming it to "IN_PROGRESS""#
+                    .to_string(),
+            ),
+        ),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+}
+
+#[test]
+fn test_check_unique() {
+    let schema = include_str!("./unique.graphql");
+    let check_result = stackup_lint::check(schema);
+
+    let list_of_scalars = PositionedComment::new(
+        Pos { line: 5, column: 3 },
+        Comment::new(
+            Severity::Warning,
+            r#"List of Scalars are not supported You may want an association instead"#.to_string(),
+        ),
+    );
+    let id = PositionedComment::new(
+        Pos { line: 2, column: 3 },
+        Comment::new(
+            Severity::Error,
+            r#"Field "id" is already unique, remove "@unique""#.to_string(),
+        ),
+    );
+    let nullable = PositionedComment::new(
+        Pos { line: 4, column: 3 },
+        Comment::new(
+            Severity::Warning,
+            r#"Unique field "nickname" is nullable, so several rows can leave it empty. Consider making this "nickname: String!""#
+                .to_string(),
+        ),
+    );
+    let list = PositionedComment::new(
+        Pos { line: 5, column: 3 },
+        Comment::new(
+            Severity::Error,
+            r#""@unique" isn't supported on list field "tags""#.to_string(),
+        ),
+    );
+    let file = PositionedComment::new(
+        Pos { line: 6, column: 3 },
+        Comment::new(
+            Severity::Error,
+            r#""@unique" isn't supported on File field "avatar""#.to_string(),
+        ),
+    );
+
+    let comments = vec![
+        list_of_scalars.clone(),
+        id.clone(),
+        nullable,
+        list.clone(),
+        file.clone(),
+        reserved_model_name(1, "User"),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+
+    let config = Config::from_toml(
+        r#"
+        [nullability]
+        unique = "any"
+        "#,
+    )
+    .unwrap();
+    let check_result = stackup_lint::check_with_config(schema, &config);
+
+    let comments = vec![
+        list_of_scalars,
+        id,
+        list,
+        file,
+        reserved_model_name(1, "User"),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+}
+
+#[test]
+fn test_check_authentication() {
+    let schema = include_str!("./authentication.graphql");
+    let check_result = stackup_lint::check(schema);
+
+    let duplicate = PositionedComment::new(
+        Pos { line: 7, column: 1 },
+        Comment::new(
+            Severity::Error,
+            r#"Only one type can be "@authenticate", "User" is already authenticated"#.to_string(),
+        ),
+    )
+    .with_related(
+        Pos { line: 1, column: 1 },
+        r#""User" is marked "@authenticate" here"#.to_string(),
+    );
+    let password = PositionedComment::new(
+        Pos { line: 4, column: 3 },
+        Comment::new(
+            Severity::Error,
+            r#"Field "passwordDigest" collides with the "password_digest" column generated for "@authenticate""#
+                .to_string(),
+        ),
+    );
+
+    let comments = vec![
+        duplicate.clone(),
+        PositionedComment::new(
+            Pos { line: 3, column: 3 },
+            Comment::new(
+                Severity::Error,
+                r#"Login field "email" should be declared as "email: String! @unique""#.to_string(),
+            ),
+        ),
+        password.clone(),
+        PositionedComment::new(
+            Pos { line: 7, column: 1 },
+            Comment::new(
+                Severity::Error,
+                r#"Type "Admin" is authenticated by "email", consider adding "email: String! @unique""#
+                    .to_string(),
+            ),
+        ),
+        reserved_model_name(1, "User"),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+
+    let config = Config::from_toml(
+        r#"
+        [authentication]
+        login_field = "username"
+        "#,
+    )
+    .unwrap();
+    let check_result = stackup_lint::check_with_config(schema, &config);
+
+    let comments = vec![
+        duplicate,
+        PositionedComment::new(
+            Pos { line: 1, column: 1 },
+            Comment::new(
+                Severity::Error,
+                r#"Type "User" is authenticated by "username", consider adding "username: String! @unique""#
+                    .to_string(),
+            ),
+        ),
+        password,
+        reserved_model_name(1, "User"),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+}
+
+#[test]
+fn test_check_association_graph() {
+    let schema = include_str!("./graph.graphql");
+    let check_result = stackup_lint::check(schema);
+    let comments = vec![reserved_model_name(1, "User")];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+
+    let config = Config::from_toml(
+        r#"
+        [graph]
+        orphans = true
+        disconnected = true
+        allow = ["AuditLog"]
+        "#,
+    )
+    .unwrap();
+    let check_result = stackup_lint::check_with_config(schema, &config);
+
+    let comments = vec![
+        PositionedComment::new(
+            Pos {
+                line: 32,
+                column: 1,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Type "LegacyImport" isn't associated with any other type"#.to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 22,
+                column: 1,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Types "Coupon", "Redemption" aren't connected to the rest of the schema"#
+                    .to_string(),
+            ),
+        )
+        .with_related(
+            Pos {
+                line: 27,
+                column: 1,
+            },
+            r#""Redemption" is declared here"#.to_string(),
+        ),
+        reserved_model_name(1, "User"),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+}
+
+#[test]
+fn test_check_heuristics() {
+    let schema = include_str!("./heuristics.graphql");
+    let check_result = stackup_lint::check(schema);
+
+    let type_heuristic = |line, message: &str, code, original: &str, replacement: &str| {
+        let pos = Pos { line, column: 3 };
+        PositionedComment::new(pos, Comment::new(Severity::Warning, message.to_string()))
+            .with_code(code)
+            .with_fix(
+                Fix::new(format!(r#"replace "{}" with "{}""#, original, replacement))
+                    .with_edit(Edit::new(pos, original, replacement)),
+            )
+    };
+    let timestamp = type_heuristic(
+        3,
+        r#"Field "createdAt" looks like a timestamp, consider making it a "DateTime" instead of a "String""#,
+        "timestamps",
+        "createdAt: String!",
+        "createdAt: DateTime!",
+    );
+    let date = type_heuristic(
+        5,
+        r#"Field "deliveredOn" looks like a date, consider making it a "Date" instead of a "DateTime""#,
+        "dates",
+        "deliveredOn: DateTime",
+        "deliveredOn: Date",
+    );
+    let money = type_heuristic(
+        7,
+        r#"Field "totalPrice" looks like a money amount, consider making it a "Decimal" instead of a "Float""#,
+        "money",
+        "totalPrice: Float!",
+        "totalPrice: Decimal!",
+    );
+
+    // the date-like list is only reported as a list of scalars
+    let list_of_scalars = PositionedComment::new(
+        Pos {
+            line: 13,
+            column: 3,
+        },
+        Comment::new(
+            Severity::Warning,
+            r#"List of Scalars are not supported You may want an association instead"#.to_string(),
+        ),
+    );
+    let generated = PositionedComment::new(
+        Pos { line: 3, column: 3 },
+        Comment::new(
+            Severity::Error,
+            r#"Field "createdAt" on "Order" collides with the field Stackup generates for every type"#
+                .to_string(),
+        ),
+    );
+
+    let comments = vec![
+        list_of_scalars.clone(),
+        timestamp,
+        date.clone(),
+        money.clone(),
+        generated.clone(),
+        reserved_model_name(1, "Order"),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+
+    let config = Config::from_toml(
+        r#"
+        [heuristics]
+        timestamps = false
+        booleans = true
+        "#,
+    )
+    .unwrap();
+    let check_result = stackup_lint::check_with_config(schema, &config);
+
+    let comments = vec![
+        list_of_scalars,
+        date,
+        money,
+        PositionedComment::new(
+            Pos {
+                line: 10,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Boolean field "paid" doesn't read as a predicate, consider a name like "isPaid" or "hasPaid""#
+                    .to_string(),
+            ),
+        )
+        .with_code("booleans"),
+        PositionedComment::new(
+            Pos {
+                line: 12,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Boolean field "express" doesn't read as a predicate, consider a name like "isExpress" or "hasExpress""#
+                    .to_string(),
+            ),
+        )
+        .with_code("booleans"),
+        generated,
+        reserved_model_name(1, "Order"),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+}
+
+#[test]
+fn test_check_cardinality() {
+    let schema = include_str!("./cardinality.graphql");
+    let check_result = stackup_lint::check(schema);
+
+    let comments = vec![
+        PositionedComment::new(
+            Pos { line: 3, column: 3 },
+            Comment::new(
+                Severity::Warning,
+                r#"List field "book" should have a plural name, consider renaming it to "books""#
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 10,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Field "authors" isn't a list and should have a singular name, consider renaming it to "author""#
+                    .to_string(),
+            ),
+        )
+        .with_fix(Fix::rename(
+            Pos {
+                line: 10,
+                column: 3,
+            },
+            "authors",
+            "author",
+        )),
+        PositionedComment::new(
+            Pos {
+                line: 11,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Field "tags" isn't a list and should have a singular name, consider renaming it to "tag""#
+                    .to_string(),
+            ),
+        )
+        // "tags" on Author is the inverse named by Label, not this one
+        .with_fix(Fix::rename(
+            Pos {
+                line: 11,
+                column: 3,
+            },
+            "tags",
+            "tag",
+        )),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+
+    let fixed = check_result.apply_fixes();
+    assert!(fixed.contains("  tag: Tag @belongsTo\n"));
+}
+
+#[test]
+fn test_check_generated_names() {
+    let schema = include_str!("./generated-names.graphql");
+    let check_result = stackup_lint::check(schema);
+
+    let unused_enum = PositionedComment::new(
+        Pos {
+            line: 12,
+            column: 1,
+        },
+        Comment::new(
+            Severity::Warning,
+            r#"Enum "BookFilter" is not used by any field"#.to_string(),
+        ),
+    );
+    let book = || {
+        (
+            Pos { line: 1, column: 1 },
+            r#""Book" is declared here"#.to_string(),
+        )
+    };
+
+    let comments = vec![
+        unused_enum.clone(),
+        PositionedComment::new(
+            Pos { line: 7, column: 1 },
+            Comment::new(
+                Severity::Error,
+                r#"Type "CreateBookInput" collides with the "Create{Type}Input" type Stackup generates for "Book""#
+                    .to_string(),
+            ),
+        )
+        .with_related(book().0, book().1),
+        PositionedComment::new(
+            Pos {
+                line: 12,
+                column: 1,
+            },
+            Comment::new(
+                Severity::Error,
+                r#"Type "BookFilter" collides with the "{Type}Filter" type Stackup generates for "Book""#
+                    .to_string(),
+            ),
+        )
+        .with_related(book().0, book().1),
+        PositionedComment::new(
+            Pos { line: 4, column: 3 },
+            Comment::new(
+                Severity::Error,
+                r#"Field "createdAt" on "Book" collides with the field Stackup generates for every type"#
+                    .to_string(),
+            ),
+        ),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+
+    let config = Config::from_toml(
+        r#"
+        [generated_names]
+        types = ["{Types}Page"]
+        fields = []
+        "#,
+    )
+    .unwrap();
+    let check_result = stackup_lint::check_with_config(schema, &config);
+
+    let comments = vec![
+        unused_enum,
+        PositionedComment::new(
+            Pos {
+                line: 17,
+                column: 1,
+            },
+            Comment::new(
+                Severity::Error,
+                r#"Type "BooksPage" collides with the "{Types}Page" type Stackup generates for "Book""#
+                    .to_string(),
+            ),
+        )
+        .with_related(book().0, book().1),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+}
+
+#[test]
+fn test_check_documentation() {
+    let schema = include_str!("./documentation.graphql");
+    let check_result = stackup_lint::check(schema);
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), vec![]));
+
+    let config = Config::from_toml(
+        r#"
+        [documentation]
+        enabled = true
+        fields = true
+        enum_values = true
+        min_coverage = 80.0
+        "#,
+    )
+    .unwrap();
+    let check_result = stackup_lint::check_with_config(schema, &config);
+
+    let coverage = Coverage {
+        documented: 4,
+        total: 7,
+    };
+    let comments = vec![
+        PositionedComment::new(
+            Pos { line: 1, column: 1 },
+            Comment::new(
+                Severity::Error,
+                "Documentation coverage is 57.1% (4 of 7 documented), below the minimum of 80%"
+                    .to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos { line: 6, column: 3 },
+            Comment::new(
+                Severity::Warning,
+                r#"Field "genre" on "Book" has no description"#.to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos { line: 9, column: 1 },
+            Comment::new(
+                Severity::Warning,
+                r#"Type "Author" has no description"#.to_string(),
+            ),
+        ),
+        PositionedComment::new(
+            Pos {
+                line: 17,
+                column: 3,
+            },
+            Comment::new(
+                Severity::Warning,
+                r#"Enum value "NON_FICTION" on "Genre" has no description"#.to_string(),
+            ),
+        ),
+    ];
+    let summary = Summary {
+        documentation: Some(coverage),
+    };
+    assert_eq!(
+        check_result,
+        CheckResult::new(schema.to_string(), comments).with_summary(summary.clone())
+    );
+
+    let config = Config::from_toml(
+        r#"
+        [documentation]
+        enabled = true
+        fields = true
+        enum_values = true
+        min_coverage = 50.0
+        "#,
+    )
+    .unwrap();
+    let check_result = stackup_lint::check_with_config(schema, &config);
+    assert_eq!(
+        check_result,
+        CheckResult::new(schema.to_string(), vec![]).with_summary(summary)
+    );
+    assert_eq!(
+        check_result.to_string(),
+        "Documentation coverage: 57.1% (4 of 7 documented)"
+    );
+    // the summary doesn't change the shape of the comments
+    assert_eq!(check_result.to_json().unwrap(), "[]");
+    assert_eq!(
+        check_result.to_json_with_summary().unwrap(),
+        r#"{
+  "comments": [],
+  "summary": {
+    "documentation": {
+      "documented": 4,
+      "total": 7
+    }
+  }
+}"#
+    );
+}
+
+#[test]
+fn test_check_limits() {
+    let schema = include_str!("./limits.graphql");
+    let check_result = stackup_lint::check(schema);
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), vec![]));
+
+    let config = Config::from_toml(
+        r#"
+        [limits]
+        max_fields = 5
+        max_associations = 1
+        max_enum_values = 4
+        max_types = 4
+        max_association_depth = 2
+        "#,
+    )
+    .unwrap();
+    let check_result = stackup_lint::check_with_config(schema, &config);
+
+    let warning = |line, message: &str| {
+        PositionedComment::new(
+            Pos { line, column: 1 },
+            Comment::new(Severity::Warning, message.to_string()),
+        )
+    };
+    let comments = vec![
+        warning(29, "The schema declares 5 types, more than the limit of 4"),
+        warning(
+            7,
+            r#"Type "Author" has 2 associations, more than the limit of 1"#,
+        ),
+        warning(14, r#"Type "Book" has 6 fields, more than the limit of 5"#),
+        warning(
+            14,
+            r#"Type "Book" has 2 associations, more than the limit of 1"#,
+        ),
+        warning(
+            29,
+            r#"Enum "Rating" has 5 values, more than the limit of 4"#,
+        ),
+        warning(
+            23,
+            "Association chain Review -> Book -> Author -> Publisher is 3 associations deep, \
+             more than the limit of 2",
+        ),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+
+    // both chains run through the Team and Player cycle, whichever
+    // of the two types they reach it by
+    let schema = include_str!("./association-depth.graphql");
+    let config = Config::from_toml(
+        r#"
+        [limits]
+        max_association_depth = 3
+        "#,
+    )
+    .unwrap();
+    let check_result = stackup_lint::check_with_config(schema, &config);
+
+    let comments = vec![
+        warning(
+            1,
+            "Association chain Transfer -> (Team, Player) -> League -> Country -> Region \
+             is 5 associations deep, more than the limit of 3",
+        ),
+        warning(
+            44,
+            "Association chain ScoutReport -> (Team, Player) -> League -> Country -> Region \
+             is 5 associations deep, more than the limit of 3",
+        ),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+}
+
+#[test]
+fn test_check_custom_rules() {
+    let schema = include_str!("./custom-rules.graphql");
+    let config = Config::from_toml(
+        r#"
+        [[rules]]
+        code = "sign-in-tracking"
+        severity = "error"
+        message = "Authenticated type {type} must track lastSignInAt"
+        match = { directive = "authenticate" }
+        require = { field = "lastSignInAt" }
+
+        [[rules]]
+        code = "url-fields"
+        message = "{field} on {type} should be a String"
+        match = { field = "*Url" }
+        require = { type = "String" }
+
+        [[rules]]
+        code = "no-legacy"
+        message = "Drop the legacy fields from {type}"
+        forbid = { field = "legacy*" }
+        "#,
+    )
+    .unwrap();
+    let check_result = stackup_lint::check_with_config(schema, &config);
+
+    let comments = vec![
+        reserved_model_name(1, "User"),
+        PositionedComment::new(
+            Pos { line: 1, column: 1 },
+            Comment::new(
+                Severity::Error,
+                "Authenticated type User must track lastSignInAt".to_string(),
+            ),
+        )
+        .with_code("sign-in-tracking"),
+        PositionedComment::new(
+            Pos { line: 9, column: 3 },
+            Comment::new(
+                Severity::Warning,
+                "websiteUrl on Company should be a String".to_string(),
+            ),
+        )
+        .with_code("url-fields"),
+        PositionedComment::new(
+            Pos { line: 7, column: 1 },
+            Comment::new(
+                Severity::Warning,
+                "Drop the legacy fields from Company".to_string(),
+            ),
+        )
+        .with_code("no-legacy"),
+    ];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+
+    let error = Config::from_toml(
+        r#"
+        [[rules]]
+        code = "empty"
+        message = "Nothing to check"
+        "#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        r#"rule "empty" doesn't require or forbid anything"#
+    );
+
+    let error = Config::from_toml(
+        r#"
+        [[rules]]
+        code = "url-fields"
+        message = "{field} on {type} should be a String"
+        match = { field = "*Url" }
+        require = { field = "url" }
+        "#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        r#"rule "url-fields" matches fields, "require.field" doesn't apply to them"#
+    );
+
+    let error = Config::from_toml(
+        r#"
+        [[rules]]
+        code = "no-floats"
+        message = "{type} shouldn't use floats"
+        match = { type = "*" }
+        forbid = { type = "Float" }
+        "#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        r#"rule "no-floats" matches types, "forbid.type" doesn't apply to them"#
+    );
+}
+
+#[test]
+fn test_check_plugins() {
+    // built into its own target directory so it doesn't wait on the
+    // lock held by the cargo invocation running this test
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("plugins");
+    // each build replaces the library, so it's copied before the next one
+    let build = |features: &str, name: &str| {
+        let status = Command::new(env!("CARGO"))
+            .args(["build", "--quiet", "-p", "stackup-lint-example-plugin"])
+            .args(["--features", features])
+            .arg("--target-dir")
+            .arg(&target_dir)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let library = target_dir.join("debug").join(format!(
+            "{}stackup_lint_example_plugin{}",
+            DLL_PREFIX, DLL_SUFFIX
+        ));
+        let path = target_dir.join(format!("{}{}", name, DLL_SUFFIX));
+        std::fs::copy(library, &path).unwrap();
+        path
+    };
+    let path = build("", "example");
+    let incompatible_path = build(
+        "stackup-lint-example-plugin/incompatible-abi",
+        "incompatible",
+    );
+
+    let plugin = Plugin::load(&path).unwrap();
+
+    let schema = include_str!("./plugin.graphql");
+    let check_result = stackup_lint::check_with_plugins(schema, &Config::default(), &[plugin]);
+
+    let comments = vec![PositionedComment::new(
+        Pos { line: 1, column: 1 },
+        Comment::new(
+            Severity::Warning,
+            r#"Type "Tag" has no fields besides "id""#.to_string(),
+        ),
+    )
+    .with_code("example/empty-type")];
+    assert_eq!(check_result, CheckResult::new(schema.to_string(), comments));
+
+    let error = Plugin::load("missing-plugin.so").err().unwrap();
+    assert!(error
+        .to_string()
+        .starts_with("Can't load plugin missing-plugin.so: "));
+
+    let error = Plugin::load(&incompatible_path).err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "Can't load plugin {}: it was built for plugin ABI version {}, this stackup-lint \
+             supports version {}. Rebuild the plugin against a matching version of stackup-lint",
+            incompatible_path.display(),
+            ABI_VERSION + 1,
+            ABI_VERSION
+        )
+    );
+}
+
+#[test]
+fn test_check_in_parallel() {
+    let schema = include_str!("./test.graphql");
+    let check_on = |threads| {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap()
+            .install(|| stackup_lint::check(schema))
+    };
+    assert_eq!(check_on(1), check_on(8));
+
+    // the binary prints the files in path order, each one's
+    // comments by position and then by rule code
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+    let output = Command::new(env!("CARGO_BIN_EXE_stackup-lint"))
+        .args(["--jobs", "4"])
+        .arg(dir.join("test.graphql"))
+        .arg(dir.join("plugin.graphql"))
+        .arg(dir.join("heuristics.graphql"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let mut files: Vec<(&str, Vec<_>)> = Vec::new();
+    for line in stdout.lines() {
+        if line.ends_with(".graphql") {
+            let name = Path::new(line).file_name().unwrap().to_str().unwrap();
+            files.push((name, Vec::new()));
+        } else if let Some(comment) = line.strip_prefix('[') {
+            let (pos, comment) = comment.split_once("] (").unwrap();
+            let (line, column) = pos.split_once(':').unwrap();
+            let severity = comment.split(" - ").next().unwrap();
+            let code = severity
+                .split_once('[')
+                .map(|(_, code)| code.trim_end_matches(']'));
+            let pos = (line.parse().unwrap(), column.parse().unwrap());
+            files.last_mut().unwrap().1.push((pos, code));
+        }
+    }
+
+    let names: Vec<_> = files.iter().map(|(name, _)| *name).collect();
+    assert_eq!(
+        names,
+        ["heuristics.graphql", "plugin.graphql", "test.graphql"]
+    );
+    let counts: Vec<_> = files.iter().map(|(_, comments)| comments.len()).collect();
+    assert_eq!(counts, [6, 0, 8]);
+    for (_, comments) in &files {
+        assert!(comments.windows(2).all(|w| w[0] <= w[1]));
+    }
+    // the heuristic is reported before the generated name collision,
+    // but a comment without a code comes first at the same position
+    assert_eq!(
+        files[0].1[1..3],
+        [((3, 3), None), ((3, 3), Some("timestamps"))]
+    );
+}
+
+#[test]
+fn test_schema_model() {
+    let source = include_str!("./schema-model.graphql");
+    let schema = Schema::parse(source, &Config::default()).unwrap();
+
+    let author = schema.object("Author").unwrap();
+    let books = author.field("books").unwrap();
+    assert_eq!(books.field_type.to_string(), "[Book!]!");
+    assert_eq!(books.kind, Kind::Object);
+    assert_eq!(
+        books.association,
+        Some(Association {
+            kind: AssociationKind::HasMany,
+            target: "Book".to_string(),
+            inverse: Some("author".to_string()),
+        })
+    );
+    assert_eq!(
+        author.field("profile").unwrap().association,
+        Some(Association {
+            kind: AssociationKind::HasOne,
+            target: "Profile".to_string(),
+            inverse: Some("author".to_string()),
+        })
+    );
+
+    let profile = schema.object("Profile").unwrap();
+    assert_eq!(
+        profile.field("author").unwrap().association,
+        Some(Association {
+            kind: AssociationKind::BelongsTo,
+            target: "Author".to_string(),
+            inverse: Some("profile".to_string()),
+        })
+    );
+
+    let book = schema.object("Book").unwrap();
+    let genre = book.field("genre").unwrap();
+    assert_eq!(genre.kind, Kind::Enum);
+    assert_eq!(genre.association, None);
+    assert_eq!(
+        genre.directive("column").unwrap().argument("default"),
+        Some(&Value::Enum("NONFICTION".to_string()))
+    );
+    assert_eq!(book.field("title").unwrap().kind, Kind::Scalar);
+    assert_eq!(
+        book.field("editor").unwrap().association,
+        Some(Association {
+            kind: AssociationKind::BelongsTo,
+            target: "Author".to_string(),
+            inverse: Some("editedBooks".to_string()),
+        })
+    );
+    assert_eq!(book.field("tags").unwrap().kind, Kind::Unknown);
+    assert_eq!(book.field("tags").unwrap().association, None);
+}